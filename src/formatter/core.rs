@@ -1,10 +1,55 @@
 use crate::formatter::datetime;
-use crate::formatter::exponential;
+use crate::formatter::general;
 use crate::formatter::text;
-use crate::types::{FormatSection, FormatToken, LocaleSettings};
+use crate::types::{
+    Decimal, FormatOptions, FormatSection, FormatToken, LocaleSettings, RoundingMode, ValueKind,
+};
 
 use crate::formatter::empty_section;
 
+/// How `format_value` interprets a value for the given section
+pub(super) fn value_kind(section: &FormatSection) -> ValueKind {
+    if section.tokens.contains(&FormatToken::GeneralNumeric) {
+        ValueKind::Number
+    } else if datetime::section_is_duration(section) {
+        ValueKind::Duration
+    } else if datetime::section_is_datetime_point_in_time(section) {
+        ValueKind::Date
+    } else if section.has_text_format {
+        ValueKind::Text
+    } else if super::fraction::analyze_fraction_pattern(section).is_fraction_format {
+        ValueKind::Fraction
+    } else {
+        ValueKind::Number
+    }
+}
+
+/// Whether the section shows only literals, with no placeholder for the value
+fn is_literal_only(section: &FormatSection) -> bool {
+    !section.tokens.iter().any(|token| {
+        matches!(
+            token,
+            FormatToken::DigitOrZero
+                | FormatToken::DigitIfNeeded
+                | FormatToken::DigitOrSpace
+                | FormatToken::DecimalPoint
+                | FormatToken::Percentage
+                | FormatToken::Exponential(_)
+                | FormatToken::TextValue // TextValue indicates it's not purely literal in this context
+        )
+    })
+}
+
+/// Whether the value's digits are laid out on digit placeholders, as in `#,##0.00` or `0.0E+0`
+///
+/// These are the sections that `format_decimal` formats without going through an `f64`.
+pub(super) fn is_placeholder_numeric(section: &FormatSection) -> bool {
+    !empty_section::is_empty_section(section.tokens.len())
+        && value_kind(section) == ValueKind::Number
+        && !section.tokens.contains(&FormatToken::GeneralNumeric)
+        && !is_literal_only(section)
+}
+
 /// Format a numeric value using the specified format section
 pub(super) fn format_value(
     original_value_for_sign: f64,
@@ -12,274 +57,96 @@ pub(super) fn format_value(
     section: &FormatSection,
     locale: &LocaleSettings,
     is_positive_section_fallback_for_negative: bool, // True if positive_section is used for a negative original_value
+    options: &FormatOptions,
 ) -> String {
     // Handle completely empty section (like in ;;;)
     if empty_section::is_empty_section(section.tokens.len()) {
         return empty_section::format_empty_section();
     }
 
-    // Handle empty format string as General
-    if section.tokens.is_empty() {
-        if original_value_for_sign.is_nan() {
-            return "NaN".to_string();
-        }
-        if original_value_for_sign.is_infinite() {
-            return if original_value_for_sign.is_sign_positive() {
-                "Infinity"
-            } else {
-                "-Infinity"
-            }
-            .to_string();
-        }
-        if original_value_for_sign == 0.0 {
-            return "0".to_string();
-        }
-
-        let abs_val = original_value_for_sign.abs();
-        let mut s_val;
-        let use_scientific = abs_val >= 1E11 || (abs_val < 1E-4 && abs_val != 0.0);
+    // Excel shows numbers from the first 15 significant digits of the stored double
+    let displayed_value = if options.excel_precision {
+        excel_precision(original_value_for_sign)
+    } else {
+        original_value_for_sign
+    };
 
-        if use_scientific {
-            s_val = format!("{:.6E}", original_value_for_sign);
-            if let Some(e_pos) = s_val.find('e').or_else(|| s_val.find('E')) {
-                let (mantissa, mut exponent_part) = s_val.split_at(e_pos);
-                exponent_part = exponent_part
-                    .trim_start_matches('E')
-                    .trim_start_matches('e');
-                let sign = if exponent_part.starts_with('-') {
-                    '-'
-                } else {
-                    '+'
-                };
-                let num_str = exponent_part.trim_start_matches(['+', '-']);
-                if let Ok(num) = num_str.parse::<i32>() {
-                    s_val = format!("{}E{}{:02}", mantissa, sign, num.abs());
-                }
-            } else {
-                s_val = original_value_for_sign.to_string().replace('e', "E"); // Fallback
-            }
-        } else {
-            s_val = original_value_for_sign.to_string();
-            let effective_len_check = if original_value_for_sign < 0.0 {
-                12
+    let kind = value_kind(section);
+    match kind {
+        ValueKind::Duration => {
+            // With signed durations, a section chosen for negative values supplies its own sign
+            let duration_value = if options.signed_durations
+                && original_value_for_sign < 0.0
+                && !is_positive_section_fallback_for_negative
+            {
+                original_value_for_sign.abs()
             } else {
-                11
+                original_value_for_sign
             };
-            if s_val.contains('.') && s_val.len() > effective_len_check {
-                if let Some((full_int_part, frac_part)) = s_val.split_once('.') {
-                    let (sign_prefix, numeric_int_part_str) =
-                        if let Some(stripped) = full_int_part.strip_prefix('-') {
-                            ("-", stripped)
-                        } else {
-                            ("", full_int_part)
-                        };
-                    let numeric_int_digits_count = numeric_int_part_str.len();
-                    let allowed_frac_digits = 10_usize.saturating_sub(numeric_int_digits_count);
-                    if frac_part.len() > allowed_frac_digits {
-                        if allowed_frac_digits == 0 {
-                            s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
-                        } else {
-                            s_val = format!(
-                                "{}{}.{}",
-                                sign_prefix,
-                                numeric_int_part_str,
-                                &frac_part[..allowed_frac_digits]
-                            );
-                        }
-                    } else if allowed_frac_digits == 0 && !frac_part.is_empty() {
-                        s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
-                    }
-                }
-            }
+            return datetime::format_duration(duration_value, section, locale, options);
         }
-        return s_val;
-    }
-
-    // Handle GeneralNumeric first if it's the only token
-    if section.tokens.len() == 1 {
-        if let FormatToken::GeneralNumeric = section.tokens[0] {
-            if original_value_for_sign.is_nan() {
-                return "NaN".to_string(); // Consider locale for NaN if needed
-            }
-            if original_value_for_sign.is_infinite() {
-                return if original_value_for_sign.is_sign_positive() {
-                    "Infinity"
-                } else {
-                    "-Infinity"
-                }
-                .to_string();
-            }
-            if original_value_for_sign == 0.0 {
-                return "0".to_string();
-            }
-
-            let abs_val = original_value_for_sign.abs();
-            let mut s_val;
-
-            // Determine if scientific notation is needed
-            // Excel uses scientific for abs(value) >= 1E11 or (abs(value) < 1E-4 and non-zero)
-            // These thresholds are approximate and can depend on context/Excel version.
-            let use_scientific = abs_val >= 1E11 || (abs_val < 1E-4 && abs_val != 0.0);
-
-            if use_scientific {
-                // Format as X.YYYYYYE+ZZ (approx. 6-7 decimal places for mantissa)
-                s_val = format!("{:.6E}", original_value_for_sign);
-                // Ensure E is uppercase and exponent is two digits with sign
-                if let Some(e_pos) = s_val.find('e').or_else(|| s_val.find('E')) {
-                    let (mantissa, mut exponent_part) = s_val.split_at(e_pos);
-                    exponent_part = exponent_part
-                        .trim_start_matches('E')
-                        .trim_start_matches('e');
-                    let sign = if exponent_part.starts_with('-') {
-                        '-'
-                    } else {
-                        '+'
-                    };
-                    let num_str = exponent_part.trim_start_matches(['+', '-']);
-                    if let Ok(num) = num_str.parse::<i32>() {
-                        s_val = format!("{}E{}{:02}", mantissa, sign, num.abs());
-                    } else {
-                        // Fallback if exponent parsing fails, just ensure E is uppercase
-                        s_val = s_val.replace('e', "E");
-                    }
-                } else {
-                    // Should not happen if format! worked, but as a fallback
-                    s_val = original_value_for_sign.to_string().replace('e', "E");
-                }
-            } else {
-                s_val = original_value_for_sign.to_string();
-                // For non-scientific, f64::to_string() is generally good.
-                // It removes trailing .0 for whole numbers.
-                // Targetting around 10 significant digits for General format.
-
-                // If s_val contains a decimal point and its total length is too long for ~10 sig digits.
-                let effective_len_check = if original_value_for_sign < 0.0 {
-                    12
-                } else {
-                    11
-                };
-
-                if s_val.contains('.') && s_val.len() > effective_len_check {
-                    if let Some((full_int_part, frac_part)) = s_val.split_once('.') {
-                        let (sign_prefix, numeric_int_part_str) =
-                            if let Some(stripped) = full_int_part.strip_prefix('-') {
-                                ("-", stripped)
-                            } else {
-                                ("", full_int_part)
-                            };
-
-                        let numeric_int_digits_count = numeric_int_part_str.len();
-
-                        let allowed_frac_digits = 10_usize.saturating_sub(numeric_int_digits_count);
-
-                        if frac_part.len() > allowed_frac_digits {
-                            if allowed_frac_digits == 0 {
-                                s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
-                            } else {
-                                s_val = format!(
-                                    "{}{}.{}",
-                                    sign_prefix,
-                                    numeric_int_part_str,
-                                    &frac_part[..allowed_frac_digits]
-                                );
-                            }
-                        } else if allowed_frac_digits == 0 && !frac_part.is_empty() {
-                            s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
-                        }
-                    }
-                }
-            }
-            return s_val;
+        ValueKind::Date => {
+            return datetime::format_datetime(original_value_for_sign, section, locale, options);
+        }
+        ValueKind::Text => {
+            return text::format_text_with_section(
+                &original_value_for_sign.to_string(),
+                section,
+                locale,
+            );
         }
+        ValueKind::Fraction | ValueKind::Number => {}
     }
 
-    // Datetime and text formatting should take precedence or be handled by specific conditions
-    if datetime::section_is_duration(section) {
-        return datetime::format_duration(original_value_for_sign, section, locale);
-    }
-    if datetime::section_is_datetime_point_in_time(section) {
-        return datetime::format_datetime(original_value_for_sign, section, locale);
-    }
-    if section.has_text_format {
-        return text::format_text_with_section(
-            &original_value_for_sign.to_string(),
-            section,
-            locale,
-        );
+    // General may be combined with literals, e.g. `General" units"`
+    if section.tokens.contains(&FormatToken::GeneralNumeric) {
+        // As with other placeholders, a section chosen for negative values that
+        // writes its own `-` or parentheses shows the value without its sign
+        let section_writes_sign = section.tokens.iter().any(|token| match token {
+            FormatToken::LiteralChar(c) => matches!(c, '-' | '('),
+            FormatToken::QuotedText(text) => text.starts_with(['-', '(']),
+            _ => false,
+        });
+        let general_value = if displayed_value < 0.0
+            && !is_positive_section_fallback_for_negative
+            && section_writes_sign
+        {
+            displayed_value.abs()
+        } else {
+            displayed_value
+        };
+        return general::format_general_section(general_value, section, locale);
     }
 
-    let analysis = super::fraction::analyze_fraction_pattern(section);
-    if analysis.is_fraction_format {
-        let mut only_placeholders_and_slash = true;
-        let mut seen_slash_in_tokens = false;
-        if analysis.has_explicit_slash {
-            for token in &section.tokens {
-                match token {
-                    FormatToken::DigitOrZero
-                    | FormatToken::DigitIfNeeded
-                    | FormatToken::DigitOrSpace => {}
-                    FormatToken::LiteralChar('/') => {
-                        seen_slash_in_tokens = true;
-                    }
-                    FormatToken::LiteralChar(' ') => {}
-                    _ => {
-                        only_placeholders_and_slash = false;
-                        break;
-                    }
-                }
-            }
-            if !seen_slash_in_tokens {
-                only_placeholders_and_slash = false;
-            }
-        } else if analysis.fixed_denominator_value.is_some() {
-            for token in &section.tokens {
-                match token {
-                    FormatToken::DigitOrZero
-                    | FormatToken::DigitIfNeeded
-                    | FormatToken::DigitOrSpace => {}
-                    FormatToken::LiteralChar(' ') => {}
-                    _ => {
-                        only_placeholders_and_slash = false;
-                        break;
-                    }
-                }
-            }
-        } else {
-            only_placeholders_and_slash = false;
-        }
+    let (original_value_for_sign, value_to_format_placeholders) = if options.excel_precision {
+        (
+            displayed_value,
+            excel_precision(value_to_format_placeholders),
+        )
+    } else {
+        (original_value_for_sign, value_to_format_placeholders)
+    };
 
-        if let Some(fraction_result) = super::fraction::format_number_as_fraction(
-            original_value_for_sign,
-            value_to_format_placeholders,
+    // Whole numbers of any size keep all their digits in front of the fraction
+    let magnitude = value_to_format_placeholders.abs();
+    if kind == ValueKind::Fraction
+        && magnitude.is_finite()
+        && let Some(result) = format_fraction_section(
+            original_value_for_sign < 0.0,
+            &magnitude.trunc().to_string(),
+            magnitude.fract(),
+            section,
             locale,
-            &analysis.integer_part_tokens,
-            &analysis.numerator_tokens,
-            &analysis.denominator_tokens,
-            analysis.fixed_denominator_value,
-            analysis.has_explicit_slash,
-            only_placeholders_and_slash,
-        ) {
-            return fraction_result;
-        }
+            options.rounding_mode,
+        )
+    {
+        return result;
     }
 
     // General number formatting logic (non-fraction)
 
     // Check for sections that are purely literal characters (text output mode)
-    let is_text_output_mode = !section.tokens.iter().any(|token| {
-        matches!(
-            token,
-            FormatToken::DigitOrZero
-                | FormatToken::DigitIfNeeded
-                | FormatToken::DigitOrSpace
-                | FormatToken::DecimalPoint
-                | FormatToken::Percentage
-                | FormatToken::Exponential(_)
-                | FormatToken::TextValue // TextValue indicates it's not purely literal in this context
-        )
-    });
-
-    if is_text_output_mode {
+    if is_literal_only(section) {
         let mut result = String::new();
         for token in &section.tokens {
             match token {
@@ -312,44 +179,100 @@ pub(super) fn format_value(
         return result;
     }
 
-    let abs_value_for_formatting = value_to_format_placeholders.abs();
-    let has_percentage = section
-        .tokens
-        .iter()
-        .any(|t| matches!(t, FormatToken::Percentage));
-
-    let mut current_adjusted_value = if has_percentage {
-        abs_value_for_formatting * 100.0
+    // The digits the double stands for are rounded exactly, once, under the selected
+    // mode; with Excel precision only its first 15 significant digits are kept
+    let decimal = if options.excel_precision {
+        Decimal::from_f64_significant(original_value_for_sign, 15)
     } else {
-        abs_value_for_formatting
+        Decimal::from_f64(original_value_for_sign)
     };
-
-    if section.num_scaling_commas > 0 {
-        for _ in 0..section.num_scaling_commas {
-            current_adjusted_value /= 1000.0;
-        }
+    match decimal {
+        Some(value) => super::decimal::format_decimal_section(
+            &value,
+            section,
+            locale,
+            is_positive_section_fallback_for_negative,
+            options.rounding_mode,
+        ),
+        // Infinities and NaN have no digits to lay out
+        None => original_value_for_sign.to_string(),
     }
+}
 
-    if let Some(exp_token_idx) = section
-        .tokens
-        .iter()
-        .position(|t| matches!(t, FormatToken::Exponential(_)))
-    {
-        let value_for_exp =
-            if original_value_for_sign < 0.0 && !is_positive_section_fallback_for_negative {
-                -current_adjusted_value
-            } else {
-                current_adjusted_value
-            };
-        return exponential::format_exponential(value_for_exp, section, exp_token_idx, locale);
+/// Round a number to the 15 significant digits that Excel displays
+fn excel_precision(value: f64) -> f64 {
+    Decimal::from_f64_significant(value, 15).map_or(value, |decimal| decimal.to_f64())
+}
+
+/// Format a magnitude, given as its whole-number digits and the fraction left over, on a fraction section
+///
+/// Returns `None` if the section is not a fraction section.
+pub(super) fn format_fraction_section(
+    negative: bool,
+    whole: &str,
+    fraction: f64,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    rounding_mode: RoundingMode,
+) -> Option<String> {
+    let analysis = super::fraction::analyze_fraction_pattern(section);
+    if !analysis.is_fraction_format {
+        return None;
+    }
+    let mut only_placeholders_and_slash = true;
+    let mut seen_slash_in_tokens = false;
+    if analysis.has_explicit_slash {
+        for token in &section.tokens {
+            match token {
+                FormatToken::DigitOrZero
+                | FormatToken::DigitIfNeeded
+                | FormatToken::DigitOrSpace => {}
+                FormatToken::LiteralChar('/') => {
+                    seen_slash_in_tokens = true;
+                }
+                FormatToken::LiteralChar(' ') => {}
+                _ => {
+                    only_placeholders_and_slash = false;
+                    break;
+                }
+            }
+        }
+        if !seen_slash_in_tokens {
+            only_placeholders_and_slash = false;
+        }
+    } else if analysis.fixed_denominator_value.is_some() {
+        for token in &section.tokens {
+            match token {
+                FormatToken::DigitOrZero
+                | FormatToken::DigitIfNeeded
+                | FormatToken::DigitOrSpace => {}
+                FormatToken::LiteralChar(' ') => {}
+                _ => {
+                    only_placeholders_and_slash = false;
+                    break;
+                }
+            }
+        }
+    } else {
+        only_placeholders_and_slash = false;
     }
 
-    // Call the formatter from the standard_numeric module
-    super::standard_numeric::format_standard_numeric_core(
-        original_value_for_sign,
-        current_adjusted_value,
-        section,
+    let fraction_result = super::fraction::format_number_as_fraction(
+        negative,
+        whole,
+        fraction,
         locale,
-        is_positive_section_fallback_for_negative,
-    )
+        &analysis.integer_part_tokens,
+        &analysis.numerator_tokens,
+        &analysis.denominator_tokens,
+        analysis.fixed_denominator_value,
+        analysis.has_explicit_slash,
+        only_placeholders_and_slash,
+        rounding_mode,
+    )?;
+    // Fractions show no literals, so every digit is the value's
+    Some(match super::dbnum::section_dbnum(section) {
+        Some(style) => super::dbnum::convert_number_text(&fraction_result, style, locale),
+        None => fraction_result,
+    })
 }
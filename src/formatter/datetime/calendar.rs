@@ -0,0 +1,106 @@
+//! Conversion of Gregorian dates to other calendars
+//!
+//! Excel renders `B2` and the Hijri calendar type with the tabular (Kuwaiti)
+//! Hijri calendar: 30-year cycles with eleven leap years, months alternating
+//! between 30 and 29 days. The Thai Buddhist calendar keeps Gregorian months
+//! and days and only shifts the year.
+
+use chrono::{Datelike, NaiveDate};
+
+use super::utils::token_locale_code;
+use crate::types::{CalendarType, FormatSection, FormatToken, LocaleSettings};
+
+/// Julian day number of 1 Muharram 1 AH (16 July 622 in the Julian calendar)
+const HIJRI_EPOCH_JDN: i64 = 1_948_440;
+
+/// Julian day number of the day before 0001-01-01, which chrono counts as day 1
+const CE_EPOCH_JDN: i64 = 1_721_425;
+
+/// Offset of the Thai Buddhist era from the Gregorian year
+const BUDDHIST_ERA_OFFSET: i32 = 543;
+
+/// Year, month (1-12) and day of a date in some calendar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct CalendarDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+/// The calendar a section asks for; the last calendar tag wins
+pub(crate) fn section_calendar(section: &FormatSection) -> CalendarType {
+    section
+        .tokens
+        .iter()
+        .rev()
+        .find_map(|token| match token {
+            FormatToken::Calendar(calendar) => Some(*calendar),
+            _ => token_locale_code(token).and_then(CalendarType::from_locale_code),
+        })
+        .unwrap_or_default()
+}
+
+/// Convert a Gregorian date to the given calendar
+pub(crate) fn to_calendar_date(date: NaiveDate, calendar: CalendarType) -> CalendarDate {
+    match calendar {
+        CalendarType::Gregorian => CalendarDate {
+            year: date.year(),
+            month: date.month(),
+            day: date.day(),
+        },
+        CalendarType::ThaiBuddhist => CalendarDate {
+            year: date.year() + BUDDHIST_ERA_OFFSET,
+            month: date.month(),
+            day: date.day(),
+        },
+        CalendarType::Hijri => to_hijri(date),
+    }
+}
+
+/// Full month name in the given calendar
+pub(crate) fn month_name(month: u32, calendar: CalendarType, locale: &LocaleSettings) -> &str {
+    let index = (month - 1) as usize;
+    match calendar {
+        CalendarType::Hijri => &locale.hijri_month_names[index],
+        CalendarType::Gregorian | CalendarType::ThaiBuddhist => &locale.month_names[index],
+    }
+}
+
+/// Abbreviated month name in the given calendar
+///
+/// Hijri months have no customary abbreviations, so the full name is used.
+pub(crate) fn short_month_name(
+    month: u32,
+    calendar: CalendarType,
+    locale: &LocaleSettings,
+) -> &str {
+    let index = (month - 1) as usize;
+    match calendar {
+        CalendarType::Hijri => &locale.hijri_month_names[index],
+        CalendarType::Gregorian | CalendarType::ThaiBuddhist => &locale.short_month_names[index],
+    }
+}
+
+/// Convert a Gregorian date to the tabular Hijri calendar
+fn to_hijri(date: NaiveDate) -> CalendarDate {
+    let days = i64::from(date.num_days_from_ce()) + CE_EPOCH_JDN - HIJRI_EPOCH_JDN;
+    let year = (30 * days + 10646).div_euclid(10631);
+    let days_into_year = days - hijri_days_before(year, 1);
+    // Months alternate between 30 and 29 days, so month m starts at ceil(29.5 * (m - 1))
+    let month = (ceil_div(2 * (days_into_year - 29), 59) + 1).clamp(1, 12);
+    let day = days - hijri_days_before(year, month) + 1;
+    CalendarDate {
+        year: year as i32,
+        month: month as u32,
+        day: day as u32,
+    }
+}
+
+/// Days from the Hijri epoch to the first day of the given month
+fn hijri_days_before(year: i64, month: i64) -> i64 {
+    ceil_div(59 * (month - 1), 2) + (year - 1) * 354 + (3 + 11 * year).div_euclid(30)
+}
+
+fn ceil_div(numerator: i64, denominator: i64) -> i64 {
+    -(-numerator).div_euclid(denominator)
+}
@@ -1,9 +1,12 @@
-use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime, Timelike};
+
+use crate::types::{DateSystem, TemporalValue};
 
 /// Helper function to convert f64 Excel date to NaiveDateTime
-/// Takes into account Excel's leap year bug (1900-02-29 is valid)
-pub fn convert_f64_to_datetime(value: f64) -> Option<NaiveDateTime> {
-    if value < 0.0 {
+/// Takes into account Excel's leap year bug (1900-02-29 is valid) in the 1900 date system
+/// Returns `None` for negative serials and serials after 9999-12-31, which Excel cannot display.
+pub fn convert_f64_to_datetime(value: f64, date_system: DateSystem) -> Option<NaiveDateTime> {
+    if value < 0.0 || value >= max_serial(date_system) {
         // Excel serial dates are typically non-negative.
         // Some interpretations map negative numbers to dates before 1900-01-01,
         // but for formatting, it's often an error or undefined.
@@ -14,7 +17,11 @@ pub fn convert_f64_to_datetime(value: f64) -> Option<NaiveDateTime> {
     let time_fraction = value.fract();
 
     // Date part calculation
-    let date_part = if excel_serial_day_part == 0 {
+    let date_part = if date_system == DateSystem::Excel1904 {
+        // Serial 0 is 1904-01-01 and there is no phantom leap day
+        NaiveDate::from_ymd_opt(1904, 1, 1)?
+            .checked_add_signed(chrono::Duration::days(excel_serial_day_part))?
+    } else if excel_serial_day_part == 0 {
         // Serial 0 is conventionally 1899-12-31
         NaiveDate::from_ymd_opt(1899, 12, 31)?
     } else {
@@ -79,3 +86,107 @@ pub fn convert_f64_to_datetime(value: f64) -> Option<NaiveDateTime> {
 
     Some(NaiveDateTime::new(current_date_part, time_part))
 }
+
+/// Like `convert_f64_to_datetime`, but maps negative serials to dates before the epoch
+///
+/// Negative serials count back from 1899-12-30 (1904-01-01 in the 1904 date
+/// system) as LibreOffice does; the time of day is the distance from the
+/// previous midnight, so -0.25 is 18:00 on the day before the epoch.
+pub fn convert_f64_to_datetime_proleptic(
+    value: f64,
+    date_system: DateSystem,
+) -> Option<NaiveDateTime> {
+    if value >= 0.0 {
+        return convert_f64_to_datetime(value, date_system);
+    }
+    if !value.is_finite() {
+        return None;
+    }
+
+    let epoch = match date_system {
+        DateSystem::Excel1900 => NaiveDate::from_ymd_opt(1899, 12, 30)?,
+        DateSystem::Excel1904 => NaiveDate::from_ymd_opt(1904, 1, 1)?,
+    };
+    let days = value.floor();
+    let nanos = ((value - days) * 86_400_000_000_000.0).round() as i64;
+    epoch
+        .and_hms_opt(0, 0, 0)?
+        .checked_add_signed(chrono::Duration::try_days(days as i64)?)?
+        .checked_add_signed(chrono::Duration::nanoseconds(nanos))
+}
+
+/// Convert a NaiveDateTime to an Excel serial date, the inverse of `convert_f64_to_datetime`
+///
+/// In the 1900 date system dates from 1900-03-01 on are one higher than the day
+/// count suggests, because Excel counts the non-existent 1900-02-29 (serial 60).
+/// Returns `None` for dates before serial 0 and after 9999-12-31.
+pub fn convert_datetime_to_f64(dt: &NaiveDateTime, date_system: DateSystem) -> Option<f64> {
+    let date = dt.date();
+    let epoch = match date_system {
+        DateSystem::Excel1904 => NaiveDate::from_ymd_opt(1904, 1, 1)?,
+        // Serial 0 is 1899-12-31; after the phantom leap day the count is one ahead
+        DateSystem::Excel1900 if date >= NaiveDate::from_ymd_opt(1900, 3, 1)? => {
+            NaiveDate::from_ymd_opt(1899, 12, 30)?
+        }
+        DateSystem::Excel1900 => NaiveDate::from_ymd_opt(1899, 12, 31)?,
+    };
+    let days = (date - epoch).num_days();
+    let serial = days as f64 + time_fraction(&dt.time());
+    if days < 0 || serial >= max_serial(date_system) {
+        return None;
+    }
+    Some(serial)
+}
+
+/// Like `convert_datetime_to_f64`, but gives negative serials for dates before the epoch
+///
+/// The inverse of `convert_f64_to_datetime_proleptic`. In the 1900 date system
+/// 1899-12-30 has no serial, as the dates before it count back from there.
+pub fn convert_datetime_to_f64_proleptic(
+    dt: &NaiveDateTime,
+    date_system: DateSystem,
+) -> Option<f64> {
+    if let Some(serial) = convert_datetime_to_f64(dt, date_system) {
+        return Some(serial);
+    }
+    let epoch = match date_system {
+        DateSystem::Excel1900 => NaiveDate::from_ymd_opt(1899, 12, 30)?,
+        DateSystem::Excel1904 => NaiveDate::from_ymd_opt(1904, 1, 1)?,
+    };
+    let days = (dt.date() - epoch).num_days();
+    (days < 0).then(|| days as f64 + time_fraction(&dt.time()))
+}
+
+/// Excel serial number of a chrono value, used by `TemporalValue::to_excel_serial`
+pub(crate) fn temporal_to_excel_serial(
+    value: &TemporalValue,
+    date_system: DateSystem,
+) -> Option<f64> {
+    match value {
+        TemporalValue::Date(date) => {
+            convert_datetime_to_f64(&date.and_time(NaiveTime::MIN), date_system)
+        }
+        TemporalValue::DateTime(dt) => convert_datetime_to_f64(dt, date_system),
+        TemporalValue::Time(time) => Some(time_fraction(time)),
+        TemporalValue::Duration(duration) => {
+            let seconds = duration.num_seconds() as f64
+                + f64::from(duration.subsec_nanos()) / 1_000_000_000.0;
+            Some(seconds / 86400.0)
+        }
+    }
+}
+
+/// Fraction of a day elapsed at the given time
+pub(crate) fn time_fraction(time: &NaiveTime) -> f64 {
+    let seconds = f64::from(time.num_seconds_from_midnight())
+        + f64::from(time.nanosecond().min(999_999_999)) / 1_000_000_000.0;
+    seconds / 86400.0
+}
+
+/// First serial after 9999-12-31 in the given date system
+fn max_serial(date_system: DateSystem) -> f64 {
+    match date_system {
+        DateSystem::Excel1900 => 2_958_466.0,
+        DateSystem::Excel1904 => 2_957_004.0,
+    }
+}
@@ -1,5 +1,8 @@
-use super::utils::{count_fractional_second_digits, format_fractional_seconds};
-use crate::types::{FormatSection, FormatToken, LocaleSettings};
+use super::utils::{
+    convert_date_numerals, count_fractional_second_digits, format_fractional_seconds,
+};
+use crate::formatter::{dbnum, rounding};
+use crate::types::{FormatOptions, FormatSection, FormatToken, LocaleSettings, RoundingMode};
 
 /// Helper function to check if a section contains duration-specific tokens
 pub fn section_is_duration(section: &FormatSection) -> bool {
@@ -17,31 +20,66 @@ pub fn section_is_duration(section: &FormatSection) -> bool {
 }
 
 /// Format a duration value according to the format section
+///
+/// Elapsed time does not depend on the epoch, so both date systems give the same result.
+/// Negative values are shown as a run of `#` like Excel does, or, with
+/// `signed_durations`, as the duration of their magnitude after a minus sign.
 pub fn format_duration(
     value: f64, // Excel serial date/time value
     section: &FormatSection,
     _locale: &LocaleSettings, // Placeholder for future use
+    options: &FormatOptions,
 ) -> String {
-    if value < 0.0 {
-        return format!(
-            "ERROR: Negative value ({}) not allowed for duration format.",
-            value
-        );
-    }
-
-    // Convert Excel serial value to total seconds
-    let total_seconds_float = value * 86400.0;
-
-    // Count fractional second digits needed
+    // Round the magnitude so that carries work the same way on both sides of zero
     let num_frac_sec_digits = count_fractional_second_digits(section);
+    let rounded_total_seconds = round_seconds(
+        value.abs() * 86400.0,
+        num_frac_sec_digits,
+        value < 0.0,
+        options.rounding_mode,
+    );
+    let magnitude = format_rounded_seconds(rounded_total_seconds, section);
+
+    // A value that rounds to zero has no sign to show
+    if value >= 0.0 || rounded_total_seconds == 0.0 {
+        magnitude
+    } else if options.signed_durations {
+        format!("-{}", magnitude)
+    } else {
+        "#".repeat(magnitude.chars().count() + 1)
+    }
+}
 
-    // Round total seconds according to the desired precision
-    let rounded_total_seconds = round_seconds(total_seconds_float, num_frac_sec_digits);
-
-    // Calculate time components
+/// Format a non-negative number of seconds that is already rounded for the section
+fn format_rounded_seconds(rounded_total_seconds: f64, section: &FormatSection) -> String {
     let time_components = calculate_time_components(rounded_total_seconds);
+    format_duration_with_tokens(
+        section,
+        time_components.hours,
+        time_components.minutes,
+        time_components.minutes_total,
+        time_components.seconds,
+        time_components.seconds_total,
+        time_components.nanos,
+    )
+}
 
-    // Format duration with tokens
+/// Format a non-negative chrono duration without converting it to a serial first
+///
+/// The seconds are rounded in integer nanoseconds, so long durations keep
+/// digits an `f64` day count would lose.
+pub(crate) fn format_duration_exact(duration: chrono::Duration, section: &FormatSection) -> String {
+    let num_frac_sec_digits = count_fractional_second_digits(section).min(9);
+    let unit = 10i128.pow(9 - num_frac_sec_digits as u32);
+    let total_nanos =
+        i128::from(duration.num_seconds()) * 1_000_000_000 + i128::from(duration.subsec_nanos());
+    // Round half up to the displayed precision
+    let rounded_nanos = (total_nanos + unit / 2) / unit * unit;
+
+    let time_components = time_components_from_parts(
+        (rounded_nanos / 1_000_000_000) as i64,
+        (rounded_nanos % 1_000_000_000) as u32,
+    );
     format_duration_with_tokens(
         section,
         time_components.hours,
@@ -63,14 +101,16 @@ struct TimeComponents {
     nanos: u32,         // Nanoseconds part
 }
 
-/// Round seconds according to the desired precision
-fn round_seconds(total_seconds: f64, num_frac_digits: usize) -> f64 {
-    if num_frac_digits > 0 {
-        let rounding_multiplier = 10f64.powi(num_frac_digits as i32);
-        (total_seconds * rounding_multiplier).round() / rounding_multiplier
-    } else {
-        total_seconds.round() // Round to nearest second if no fractional part in format
-    }
+/// Round a non-negative number of seconds to the fractional digits shown
+fn round_seconds(
+    total_seconds: f64,
+    num_frac_digits: usize,
+    negative: bool,
+    mode: RoundingMode,
+) -> f64 {
+    let rounding_multiplier = 10f64.powi(num_frac_digits as i32);
+    rounding::round_magnitude(total_seconds * rounding_multiplier, negative, mode)
+        / rounding_multiplier
 }
 
 /// Calculate time components from total seconds
@@ -79,18 +119,18 @@ fn calculate_time_components(total_seconds: f64) -> TimeComponents {
     // Ensure nanos are calculated from the (potentially) rounded value's fractional part.
     let final_nanos_part = (total_seconds.fract().abs() * 1_000_000_000.0).round() as u32;
 
-    let hours = final_total_seconds_int_part / 3600;
-    let minutes_total = final_total_seconds_int_part / 60; // For [m]
-    let minutes_part = (final_total_seconds_int_part / 60) % 60;
-    let seconds_part = final_total_seconds_int_part % 60;
+    time_components_from_parts(final_total_seconds_int_part, final_nanos_part)
+}
 
+/// Split whole seconds and nanoseconds into the parts shown by duration tokens
+fn time_components_from_parts(total_seconds: i64, nanos: u32) -> TimeComponents {
     TimeComponents {
-        hours,
-        minutes: minutes_part,
-        minutes_total,
-        seconds: seconds_part,
-        seconds_total: final_total_seconds_int_part,
-        nanos: final_nanos_part,
+        hours: total_seconds / 3600,
+        minutes: (total_seconds / 60) % 60,
+        minutes_total: total_seconds / 60, // For [m]
+        seconds: total_seconds % 60,
+        seconds_total: total_seconds,
+        nanos,
     }
 }
 
@@ -106,8 +146,10 @@ fn format_duration_with_tokens(
 ) -> String {
     let mut result = String::new();
     let mut tokens_iter = section.tokens.iter().peekable();
+    let dbnum_style = dbnum::section_dbnum(section);
 
     while let Some(token) = tokens_iter.next() {
+        let token_start = result.len();
         match token {
             FormatToken::ElapsedHours => {
                 result.push_str(&hours.to_string());
@@ -151,6 +193,9 @@ fn format_duration_with_tokens(
             // in pure duration formats. They could be ignored or result in empty output for that part.
             _ => { /* Ignored in duration context for now */ }
         }
+        if let Some(style) = dbnum_style {
+            convert_date_numerals(&mut result, token_start, token, style);
+        }
     }
 
     result
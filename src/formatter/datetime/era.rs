@@ -0,0 +1,107 @@
+//! Japanese imperial eras for the `g` and `e` tokens
+//!
+//! `g`, `gg` and `ggg` show the era as an initial, a single kanji or the full
+//! name, and `e`/`ee` the year within the era, so 2024-05-06 is 令和6 with
+//! `ggge`. Dates before the first era in the table have no era; their era
+//! name is empty and their era year is the Gregorian year.
+//!
+//! The eras only apply to Japanese sections, those tagged `[$-411]` or
+//! formatted with the Japanese locale. Elsewhere `g` shows nothing and `e`
+//! the year, as Excel does.
+
+use chrono::{Datelike, NaiveDate};
+
+use super::utils::extract_locale_code;
+use crate::locale;
+use crate::types::{FormatSection, LocaleSettings};
+
+/// Windows LCID of Japanese (Japan)
+const JAPANESE_LCID: u32 = 0x411;
+
+/// Primary language ID of Japanese, the low 10 bits of its LCIDs
+const JAPANESE_LANGUAGE_ID: u32 = 0x11;
+
+/// A Japanese era and the day it began
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct JapaneseEra {
+    /// First day of the era as (year, month, day)
+    pub start: (i32, u32, u32),
+    /// Latin initial (g)
+    pub initial: &'static str,
+    /// First kanji of the name (gg)
+    pub abbreviation: &'static str,
+    /// Full name (ggg)
+    pub name: &'static str,
+}
+
+/// Eras from Meiji on, oldest first
+pub(crate) const JAPANESE_ERAS: [JapaneseEra; 5] = [
+    JapaneseEra {
+        start: (1868, 9, 8),
+        initial: "M",
+        abbreviation: "明",
+        name: "明治",
+    },
+    JapaneseEra {
+        start: (1912, 7, 30),
+        initial: "T",
+        abbreviation: "大",
+        name: "大正",
+    },
+    JapaneseEra {
+        start: (1926, 12, 25),
+        initial: "S",
+        abbreviation: "昭",
+        name: "昭和",
+    },
+    JapaneseEra {
+        start: (1989, 1, 8),
+        initial: "H",
+        abbreviation: "平",
+        name: "平成",
+    },
+    JapaneseEra {
+        start: (2019, 5, 1),
+        initial: "R",
+        abbreviation: "令",
+        name: "令和",
+    },
+];
+
+/// Whether a section shows Japanese eras
+///
+/// The section's locale tag decides; without one, the locale it is formatted with.
+pub(crate) fn uses_japanese_eras(section: &FormatSection, locale: &LocaleSettings) -> bool {
+    match extract_locale_code(&section.tokens) {
+        Some(code) => is_japanese_code(code),
+        None => locale::get_locale_settings_by_code(JAPANESE_LCID)
+            .is_some_and(|japanese| japanese == *locale),
+    }
+}
+
+/// Whether a locale tag code such as `411`, `1060411` or `ja-JP` names Japanese
+fn is_japanese_code(code: &str) -> bool {
+    match u32::from_str_radix(code, 16) {
+        Ok(lcid) => lcid & 0x3FF == JAPANESE_LANGUAGE_ID,
+        Err(_) => code
+            .split(['-', '_'])
+            .next()
+            .is_some_and(|language| language.eq_ignore_ascii_case("ja")),
+    }
+}
+
+/// The era the date falls in, if any
+pub(crate) fn japanese_era(date: NaiveDate) -> Option<&'static JapaneseEra> {
+    JAPANESE_ERAS.iter().rev().find(|era| {
+        let (year, month, day) = era.start;
+        (date.year(), date.month(), date.day()) >= (year, month, day)
+    })
+}
+
+/// Year within the date's era, counting the first (partial) year as 1
+pub(crate) fn era_year(date: NaiveDate) -> i32 {
+    match japanese_era(date) {
+        Some(era) => date.year() - era.start.0 + 1,
+        None => date.year(),
+    }
+}
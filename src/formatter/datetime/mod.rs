@@ -2,13 +2,23 @@
 //!
 //! This module handles formatting of date and time values according to Excel format patterns.
 
+mod calendar;
 mod conversion;
 mod duration;
+mod era;
 mod point_in_time;
 mod utils;
 
 // Re-export the public interface
-pub use conversion::convert_f64_to_datetime;
+pub(crate) use calendar::section_calendar;
+pub use conversion::{
+    convert_datetime_to_f64, convert_datetime_to_f64_proleptic, convert_f64_to_datetime,
+    convert_f64_to_datetime_proleptic,
+};
+pub(crate) use conversion::{temporal_to_excel_serial, time_fraction};
+pub(crate) use duration::format_duration_exact;
 pub use duration::{format_duration, section_is_duration};
 pub use point_in_time::{format_datetime, section_is_datetime_point_in_time};
+pub(crate) use point_in_time::{format_naive_datetime, invalid_date_placeholder};
+pub(crate) use utils::section_locale;
 pub use utils::special_dates;
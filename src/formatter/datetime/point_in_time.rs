@@ -1,10 +1,18 @@
-use crate::types::{AmPmStyle, FormatSection, FormatToken, LocaleSettings};
+use crate::formatter::dbnum;
+use crate::formatter::rounding::{self, Remainder};
+use crate::types::{
+    AmPmStyle, FormatOptions, FormatSection, FormatToken, LocaleSettings, RoundingMode,
+};
 use chrono::{Datelike, NaiveDateTime, Timelike};
 
 use super::{
-    conversion::convert_f64_to_datetime,
+    calendar,
+    conversion::{convert_f64_to_datetime, convert_f64_to_datetime_proleptic},
+    era,
     utils::{
-        extract_currency_prefix, format_fractional_seconds, has_fractional_seconds, special_dates,
+        convert_date_numerals, count_fractional_second_digits, extract_currency_prefix,
+        extract_locale_code, format_fractional_seconds, has_fractional_seconds, section_locale,
+        special_dates,
     },
 };
 
@@ -24,6 +32,17 @@ pub fn section_is_datetime_point_in_time(section: &FormatSection) -> bool {
                 | FormatToken::DayNumPadded
                 | FormatToken::WeekdayAbbr
                 | FormatToken::WeekdayFullName
+                | FormatToken::EraNameShort
+                | FormatToken::EraNameAbbr
+                | FormatToken::EraNameFull
+                | FormatToken::EraYear
+                | FormatToken::EraYearPadded
+                | FormatToken::Quarter
+                | FormatToken::QuarterName
+                | FormatToken::IsoWeek
+                | FormatToken::IsoWeekYear
+                | FormatToken::DayOfYear
+                | FormatToken::DayOfYearPadded
                 | FormatToken::Hour12Or24
                 | FormatToken::Hour12Or24Padded
                 | FormatToken::MinuteNum
@@ -39,97 +58,138 @@ pub fn section_is_datetime_point_in_time(section: &FormatSection) -> bool {
 }
 
 /// Format a datetime value according to the format section
-pub fn format_datetime(value: f64, section: &FormatSection, locale: &LocaleSettings) -> String {
-    // 检查和设置区域代码上下文
-    // 在一些特殊的格式如 [$-409] 或 [$-111] 中，需要提取并设置区域代码
-    for token in &section.tokens {
-        if let FormatToken::CurrencySymbolLocalePrefixed(locale_str) = token {
-            // 提取区域代码
-            if let Some(code) = extract_locale_code_from_string(locale_str) {
-                // 设置区域上下文代码
-                set_locale_context(Some(code));
-                break;
-            }
-        }
+///
+/// A locale tag such as `[$-407]` in the section replaces the month and day
+/// names and AM/PM markers of `locale` for this section only.
+pub fn format_datetime(
+    value: f64,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    // [$-111] shows the serial number itself
+    if extract_locale_code(&section.tokens) == Some("111") {
+        return value.to_string();
     }
+    let tag_locale = section_locale(section);
+    let locale = tag_locale.as_ref().unwrap_or(locale);
 
     // First check for special dates like Excel's non-existent 1900-02-29
-    if let Some(special_result) = special_dates(value, section, locale) {
-        // 清除线程本地上下文
-        set_locale_context(None);
+    if let Some(special_result) = special_dates(value, section, locale, options) {
         return special_result;
     }
 
-    let Some(dt_original) = convert_f64_to_datetime(value) else {
-        // 清除线程本地上下文
-        set_locale_context(None);
-        return format!("INVALID_DATE_SERIAL: {}", value);
+    let converted = if options.proleptic_dates {
+        convert_f64_to_datetime_proleptic(value, options.date_system)
+    } else {
+        convert_f64_to_datetime(value, options.date_system)
+    };
+    let Some(dt_original) = converted else {
+        return invalid_date_placeholder(section, locale, options);
     };
 
-    // 检查使用的区域代码
-    let locale_code = get_locale_context();
-
-    // 检查是否为特殊的 [$-111] 格式 - 根据测试用例，该格式应该返回原始值
-    if locale_code.as_deref() == Some("111") {
-        // 清除线程本地上下文
-        set_locale_context(None);
-        return value.to_string();
-    }
+    render_datetime(&dt_original, section, locale, options.rounding_mode)
+}
 
-    // Determine if format has fractional seconds and set up display datetime
-    let format_has_fractional_seconds = has_fractional_seconds(section);
+/// Format an already converted datetime, keeping its full nanosecond precision
+pub(crate) fn format_naive_datetime(
+    dt_original: &NaiveDateTime,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+) -> String {
+    let tag_locale = section_locale(section);
+    render_datetime(
+        dt_original,
+        section,
+        tag_locale.as_ref().unwrap_or(locale),
+        RoundingMode::default(),
+    )
+}
 
-    // If not formatting fractional seconds, round dt_original to the nearest second for display
-    let dt_display = if !format_has_fractional_seconds {
-        dt_original
-            .checked_add_signed(chrono::Duration::nanoseconds(500_000_000))
-            .unwrap_or(dt_original) // Fallback to original if addition fails (highly unlikely)
+/// Format a datetime with the locale already resolved for the section
+fn render_datetime(
+    dt_original: &NaiveDateTime,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    rounding_mode: RoundingMode,
+) -> String {
+    // Round to the fractional seconds shown, or to whole seconds
+    let shown_digits = if has_fractional_seconds(section) {
+        count_fractional_second_digits(section)
     } else {
-        dt_original
+        0
     };
+    let dt_display = round_to_shown_seconds(dt_original, shown_digits, rounding_mode);
 
     // Format the datetime value
-    let formatted = format_datetime_value(&dt_display, &dt_original, section, locale);
+    let formatted = format_datetime_value(&dt_display, section, locale);
 
     // Add currency prefix if present
-    if let Some(prefix) = extract_currency_prefix(&section.tokens) {
-        // 清除线程本地上下文
-        set_locale_context(None);
-        return prefix + &formatted;
+    match extract_currency_prefix(&section.tokens) {
+        Some(prefix) => prefix + &formatted,
+        None => formatted,
     }
-
-    // 清除线程本地上下文
-    set_locale_context(None);
-    formatted
 }
 
-/// 从字符串中提取区域代码
-fn extract_locale_code_from_string(value: &str) -> Option<String> {
-    // 处理格式 [$-409] 或 [$US-409] 或 [$€-409]
-    // 1. [$-409] 格式
-    if let Some(stripped) = value.strip_prefix("-") {
-        return Some(stripped.to_string());
-    }
-
-    // 2. [$US-409] 格式
-    if let Some(index) = value.find('-') {
-        if index < value.len() - 1 {
-            return Some(value[index + 1..].to_string());
-        }
+/// Round a datetime to `digits` fractional seconds
+///
+/// Dates count forward from the epoch, so `Floor` rounds to the earlier time.
+fn round_to_shown_seconds(dt: &NaiveDateTime, digits: usize, mode: RoundingMode) -> NaiveDateTime {
+    let unit = 10_u32.pow(9 - digits.min(9) as u32);
+    let nanos = dt.nanosecond() % 1_000_000_000;
+    let dropped = nanos % unit;
+    let remainder = match dropped.cmp(&(unit / 2)) {
+        _ if dropped == 0 => Remainder::Zero,
+        std::cmp::Ordering::Less => Remainder::BelowHalf,
+        std::cmp::Ordering::Equal => Remainder::Half,
+        std::cmp::Ordering::Greater => Remainder::AboveHalf,
+    };
+    let kept_units = (u64::from(dt.num_seconds_from_midnight()) * 1_000_000_000
+        + u64::from(nanos - dropped))
+        / u64::from(unit);
+    let truncated = *dt - chrono::Duration::nanoseconds(i64::from(dropped));
+    if rounding::rounds_up(mode, false, remainder, kept_units % 2 == 1) {
+        truncated
+            .checked_add_signed(chrono::Duration::nanoseconds(i64::from(unit)))
+            .unwrap_or(truncated)
+    } else {
+        truncated
     }
+}
 
-    None
+/// Excel shows a run of `#` for serials it cannot display as a date, as wide as a real date would be
+pub(crate) fn invalid_date_placeholder(
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    // Serial 1 is a valid date in both date systems
+    let sample = format_datetime(1.0, section, locale, options);
+    "#".repeat(sample.chars().count().max(1))
 }
 
 /// Format a datetime value using the given format tokens
 fn format_datetime_value(
     dt_display: &NaiveDateTime,
-    dt_original: &NaiveDateTime,
     section: &FormatSection,
     locale: &LocaleSettings,
 ) -> String {
     let mut result = String::new();
 
+    let calendar = calendar::section_calendar(section);
+    let date = calendar::to_calendar_date(dt_display.date(), calendar);
+    let dbnum_style = dbnum::section_dbnum(section);
+    let japanese_eras = section.tokens.iter().any(|token| {
+        matches!(
+            token,
+            FormatToken::EraNameShort
+                | FormatToken::EraNameAbbr
+                | FormatToken::EraNameFull
+                | FormatToken::EraYear
+                | FormatToken::EraYearPadded
+        )
+    }) && era::uses_japanese_eras(section, locale);
+
     let has_ampm_in_section = section
         .tokens
         .iter()
@@ -145,50 +205,38 @@ fn format_datetime_value(
     let mut current_token_index = 0;
     while current_token_index < section.tokens.len() {
         let token = &section.tokens[current_token_index];
+        let token_start = result.len();
 
         match token {
             // Date tokens
             FormatToken::YearTwoDigit => {
-                result.push_str(&format!("{:02}", dt_display.year() % 100));
+                result.push_str(&format!("{:02}", date.year % 100));
             }
             FormatToken::YearFourDigit => {
-                result.push_str(&dt_display.year().to_string());
+                result.push_str(&date.year.to_string());
             }
             FormatToken::MonthNum => {
-                result.push_str(&dt_display.month().to_string());
+                result.push_str(&date.month.to_string());
             }
             FormatToken::MonthNumPadded => {
-                result.push_str(&format!("{:02}", dt_display.month()));
+                result.push_str(&format!("{:02}", date.month));
             }
             FormatToken::MonthAbbr => {
-                result.push_str(&locale.short_month_names[dt_display.month0() as usize]);
+                result.push_str(calendar::short_month_name(date.month, calendar, locale));
             }
             FormatToken::MonthFullName => {
-                result.push_str(&locale.month_names[dt_display.month0() as usize]);
+                result.push_str(calendar::month_name(date.month, calendar, locale));
             }
             FormatToken::MonthLetter => {
-                let month_letter = match dt_display.month() {
-                    1 => "J",
-                    2 => "F",
-                    3 => "M",
-                    4 => "A",
-                    5 => "M",
-                    6 => "J",
-                    7 => "J",
-                    8 => "A",
-                    9 => "S",
-                    10 => "O",
-                    11 => "N",
-                    12 => "D",
-                    _ => "", // Should not happen with valid NaiveDateTime
-                };
-                result.push_str(month_letter);
+                // Capitalized, as Excel shows J for the French (West Indies) janvier
+                let name = calendar::month_name(date.month, calendar, locale);
+                result.extend(name.chars().next().into_iter().flat_map(char::to_uppercase));
             }
             FormatToken::DayNum => {
-                result.push_str(&dt_display.day().to_string());
+                result.push_str(&date.day.to_string());
             }
             FormatToken::DayNumPadded => {
-                result.push_str(&format!("{:02}", dt_display.day()));
+                result.push_str(&format!("{:02}", date.day));
             }
             FormatToken::WeekdayAbbr => {
                 result.push_str(
@@ -201,6 +249,46 @@ fn format_datetime_value(
                 );
             }
 
+            FormatToken::EraNameShort | FormatToken::EraNameAbbr | FormatToken::EraNameFull => {
+                if japanese_eras && let Some(era) = era::japanese_era(dt_display.date()) {
+                    result.push_str(match token {
+                        FormatToken::EraNameShort => era.initial,
+                        FormatToken::EraNameAbbr => era.abbreviation,
+                        _ => era.name,
+                    });
+                }
+            }
+            // Outside Japanese sections, `e` is the year of the section's calendar
+            FormatToken::EraYear | FormatToken::EraYearPadded if !japanese_eras => {
+                result.push_str(&date.year.to_string());
+            }
+            FormatToken::EraYear => {
+                result.push_str(&era::era_year(dt_display.date()).to_string());
+            }
+            FormatToken::EraYearPadded => {
+                result.push_str(&format!("{:02}", era::era_year(dt_display.date())));
+            }
+
+            // Extended tokens follow the Gregorian and ISO 8601 calendars
+            FormatToken::Quarter => {
+                result.push_str(&format!("Q{}", dt_display.month0() / 3 + 1));
+            }
+            FormatToken::QuarterName => {
+                result.push_str(&locale.quarter_names[(dt_display.month0() / 3) as usize]);
+            }
+            FormatToken::IsoWeek => {
+                result.push_str(&dt_display.iso_week().week().to_string());
+            }
+            FormatToken::IsoWeekYear => {
+                result.push_str(&format!("{:04}", dt_display.iso_week().year()));
+            }
+            FormatToken::DayOfYear => {
+                result.push_str(&dt_display.ordinal().to_string());
+            }
+            FormatToken::DayOfYearPadded => {
+                result.push_str(&format!("{:03}", dt_display.ordinal()));
+            }
+
             // Time tokens
             FormatToken::Hour12Or24 | FormatToken::Hour12Or24Padded => {
                 format_hour(
@@ -277,7 +365,7 @@ fn format_datetime_value(
                 }
 
                 if placeholders_processed_count > 0 {
-                    let nanos_val = dt_original.nanosecond(); // Use original dt for nanosecond precision
+                    let nanos_val = dt_display.nanosecond(); // Already rounded to the digits shown
                     let frac_digits =
                         format_fractional_seconds(nanos_val, placeholders_processed_count);
                     result.push_str(&frac_digits);
@@ -290,16 +378,13 @@ fn format_datetime_value(
             FormatToken::SkipWidth(_) => result.push(' '),
             _ => {}
         }
+        if let Some(style) = dbnum_style {
+            convert_date_numerals(&mut result, token_start, token, style);
+        }
         current_token_index += 1; // Advance to the next token
     }
 
-    // If result is empty, it means only datetime tokens that are still TODO were present.
-    if result.is_empty() && !section.tokens.is_empty() {
-        // Fallback if all were TODOs, return a representation of the converted datetime for debugging
-        format!("DT_CONVERTED: {:?}", dt_display) // Use dt_display for fallback
-    } else {
-        result
-    }
+    result
 }
 
 /// Format hour value considering 12/24-hour format and padding
@@ -328,22 +413,6 @@ fn format_hour(
     }
 }
 
-thread_local! {
-    static LOCALE_CONTEXT: std::cell::RefCell<Option<String>> = const { std::cell::RefCell::new(None) };
-}
-
-/// 设置当前区域上下文代码
-pub fn set_locale_context(locale_code: Option<String>) {
-    LOCALE_CONTEXT.with(|ctx| {
-        *ctx.borrow_mut() = locale_code;
-    });
-}
-
-/// 获取当前区域上下文代码
-fn get_locale_context() -> Option<String> {
-    LOCALE_CONTEXT.with(|ctx| ctx.borrow().clone())
-}
-
 /// Format AM/PM marker
 fn format_am_pm(
     result: &mut String,
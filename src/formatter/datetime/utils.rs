@@ -1,13 +1,21 @@
-use crate::types::{FormatSection, FormatToken, LocaleSettings};
+use crate::formatter::dbnum;
+use crate::locale;
+use crate::types::{
+    DateSystem, DbNumStyle, FormatOptions, FormatSection, FormatToken, LocaleSettings,
+};
 
 /// Handle special date formats like the non-existent 1900-02-29 (Excel's leap year bug)
+///
+/// The 1904 date system has no such dates.
 pub fn special_dates(
     value: f64,
     section: &FormatSection,
     locale: &LocaleSettings,
+    options: &FormatOptions,
 ) -> Option<String> {
     // Handle Excel's 1900-02-29 (serial 60) directly
-    if value.trunc() == 60.0 && value.fract() == 0.0 {
+    if options.date_system == DateSystem::Excel1900 && value.trunc() == 60.0 && value.fract() == 0.0
+    {
         let mut special_result = String::new();
         for token in &section.tokens {
             match token {
@@ -33,6 +41,51 @@ pub fn special_dates(
     None
 }
 
+/// Rewrite the digits a token has just appended to `result` for `[DBNum1]` to `[DBNum3]`
+///
+/// Years and padded numbers are written digit by digit, other numbers with
+/// positional units; literal text is left alone.
+pub fn convert_date_numerals(
+    result: &mut String,
+    token_start: usize,
+    token: &FormatToken,
+    style: DbNumStyle,
+) {
+    let padded = match token {
+        FormatToken::YearTwoDigit
+        | FormatToken::YearFourDigit
+        | FormatToken::MonthNumPadded
+        | FormatToken::MonthOrMinute2
+        | FormatToken::DayNumPadded
+        | FormatToken::EraYearPadded
+        | FormatToken::IsoWeekYear
+        | FormatToken::DayOfYearPadded
+        | FormatToken::Hour12Or24Padded
+        | FormatToken::MinuteNumPadded
+        | FormatToken::SecondNumPadded
+        | FormatToken::ElapsedHoursPadded
+        | FormatToken::ElapsedMinutesPadded
+        | FormatToken::ElapsedSecondsPadded
+        | FormatToken::DecimalPoint => true,
+        FormatToken::MonthNum
+        | FormatToken::MonthOrMinute1
+        | FormatToken::DayNum
+        | FormatToken::EraYear
+        | FormatToken::IsoWeek
+        | FormatToken::DayOfYear
+        | FormatToken::Hour12Or24
+        | FormatToken::MinuteNum
+        | FormatToken::SecondNum
+        | FormatToken::ElapsedHours
+        | FormatToken::ElapsedMinutes
+        | FormatToken::ElapsedSeconds => false,
+        _ => return,
+    };
+    let converted = dbnum::date_part(&result[token_start..], padded, style);
+    result.truncate(token_start);
+    result.push_str(&converted);
+}
+
 /// Format fractional seconds according to the specified precision
 pub fn format_fractional_seconds(nanos: u32, precision: usize) -> String {
     let mut result = String::new();
@@ -58,45 +111,38 @@ pub fn format_fractional_seconds(nanos: u32, precision: usize) -> String {
 /// Extract currency prefix from tokens if present
 pub fn extract_currency_prefix(tokens: &[FormatToken]) -> Option<String> {
     for token in tokens {
-        match token {
-            // 处理格式 [$US-409]
-            FormatToken::CurrencySymbolLocalePrefixed(value) => {
-                if let Some(index) = value.find('-') {
-                    if index > 0 {
-                        return Some(value[..index].to_string());
-                    }
-                }
-            }
-            _ => {}
+        // 处理格式 [$US-409], stored as "US:[$-409]"
+        if let FormatToken::CurrencySymbolLocalePrefixed(value) = token
+            && let Some((prefix, _)) = value.split_once(':')
+            && !prefix.is_empty()
+        {
+            return Some(prefix.to_string());
         }
     }
     None
 }
 
-/// Extract locale code from tokens if present
-#[allow(dead_code)]
-pub fn extract_locale_code(tokens: &[FormatToken]) -> Option<String> {
-    for token in tokens {
-        match token {
-            // 处理格式 [$-409] 或 [$US-409] 或 [$€-409]
-            FormatToken::CurrencySymbolLocalePrefixed(value) => {
-                // 尝试解析可能的格式
-                // 1. [$-409] 格式
-                if let Some(stripped) = value.strip_prefix("-") {
-                    return Some(stripped.to_string());
-                }
+/// Extract the locale code of the first `[$-409]` or `[$US-409]` tag, e.g. `409`
+pub fn extract_locale_code(tokens: &[FormatToken]) -> Option<&str> {
+    tokens.iter().find_map(token_locale_code)
+}
 
-                // 2. [$US-409] 格式
-                if let Some(index) = value.find('-') {
-                    if index < value.len() - 1 {
-                        return Some(value[index + 1..].to_string());
-                    }
-                }
-            }
-            _ => {}
-        }
+/// Locale code carried by a single token, if it is a locale tag
+pub fn token_locale_code(token: &FormatToken) -> Option<&str> {
+    match token {
+        FormatToken::LocaleTag(code) => Some(code),
+        // Stored as "<prefix>:[$-<code>]"
+        FormatToken::CurrencySymbolLocalePrefixed(value) => value
+            .rsplit_once(":[$-")
+            .map(|(_, code)| code.trim_end_matches(']')),
+        _ => None,
     }
-    None
+}
+
+/// Locale selected by the section's locale tag, if the tag names a known locale
+pub fn section_locale(section: &FormatSection) -> Option<LocaleSettings> {
+    let code = extract_locale_code(&section.tokens)?;
+    locale::get_locale_settings_for_excel_code(&format!("[$-{}]", code))
 }
 
 /// Count the number of fractional second digits required by the format
@@ -0,0 +1,220 @@
+//! CJK numeral substitution for `[DBNum1]`, `[DBNum2]` and `[DBNum3]`
+//!
+//! The modifiers change how the digits a section's placeholders show are
+//! written; digits in literal text and in exponents are left alone.
+//! `[DBNum1]` and `[DBNum2]` spell integers with positional units (一百二十三,
+//! 壹佰贰拾叁) and write numbers with a decimal part and years digit by digit;
+//! `[DBNum3]` only swaps in full-width digits.
+
+use std::ops::Range;
+
+use crate::types::{DbNumStyle, FormatSection, FormatToken, LocaleSettings};
+
+const DBNUM1_DIGITS: [char; 10] = ['○', '一', '二', '三', '四', '五', '六', '七', '八', '九'];
+const DBNUM2_DIGITS: [char; 10] = ['零', '壹', '贰', '叁', '肆', '伍', '陆', '柒', '捌', '玖'];
+
+/// Units for tens, hundreds and thousands within a group of four digits
+const DBNUM1_UNITS: [char; 3] = ['十', '百', '千'];
+const DBNUM2_UNITS: [char; 3] = ['拾', '佰', '仟'];
+
+/// Units for each group of four digits: 10^4, 10^8, 10^12, 10^16
+const GROUP_UNITS: [&str; 5] = ["", "万", "亿", "兆", "京"];
+
+/// The numeral modifier of a section, if any; the last one wins
+pub(crate) fn section_dbnum(section: &FormatSection) -> Option<DbNumStyle> {
+    section.tokens.iter().rev().find_map(|token| match token {
+        FormatToken::DbNum(style) => Some(*style),
+        _ => None,
+    })
+}
+
+/// Rewrite the digits of a number's output, such as a fraction or General
+pub(crate) fn convert_number_text(
+    text: &str,
+    style: DbNumStyle,
+    locale: &LocaleSettings,
+) -> String {
+    convert_placeholder_digits(text, &[], style, locale)
+}
+
+/// Rewrite the digits placeholders wrote to the output of a number section
+///
+/// `literals` are the byte ranges, in order, of text written by literal
+/// tokens, which is kept as is. If the number shows a decimal part, all its
+/// digits are written one by one; otherwise each run of digits becomes a
+/// positional numeral, dropping the thousands separators inside it.
+pub(crate) fn convert_placeholder_digits(
+    text: &str,
+    literals: &[Range<usize>],
+    style: DbNumStyle,
+    locale: &LocaleSettings,
+) -> String {
+    let mut segments: Vec<(bool, &str)> = Vec::new();
+    let mut pos = 0;
+    for range in literals {
+        segments.push((false, &text[pos..range.start]));
+        segments.push((true, &text[range.clone()]));
+        pos = range.end;
+    }
+    segments.push((false, &text[pos..]));
+
+    let has_decimals = segments.iter().any(|(is_literal, segment)| {
+        !is_literal
+            && segment
+                .split(locale.decimal_point)
+                .skip(1)
+                .any(|after| after.starts_with(|c: char| c.is_ascii_digit()))
+    });
+    segments
+        .into_iter()
+        .map(|(is_literal, segment)| {
+            if is_literal {
+                segment.to_string()
+            } else {
+                convert_segment(segment, has_decimals, style, locale)
+            }
+        })
+        .collect()
+}
+
+/// Rewrite the digits of text written by placeholders, up to an exponent
+fn convert_segment(
+    text: &str,
+    digit_by_digit: bool,
+    style: DbNumStyle,
+    locale: &LocaleSettings,
+) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut result = String::with_capacity(text.len() * 3);
+    let mut i = 0;
+    while i < chars.len() {
+        // The digits of an exponent stay as they are
+        if chars[i] == 'E' {
+            result.extend(&chars[i..]);
+            break;
+        }
+        if !chars[i].is_ascii_digit() {
+            result.push(chars[i]);
+            i += 1;
+            continue;
+        }
+
+        let mut run = String::new();
+        while i < chars.len() {
+            if chars[i].is_ascii_digit() {
+                run.push(chars[i]);
+            } else if chars[i] == locale.thousands_separator
+                && !digit_by_digit
+                && style != DbNumStyle::DbNum3
+                && chars.get(i + 1).is_some_and(char::is_ascii_digit)
+            {
+                // Grouping has no place between positional units
+            } else {
+                break;
+            }
+            i += 1;
+        }
+
+        if digit_by_digit {
+            result.push_str(&digits(&run, style));
+        } else {
+            result.push_str(&integer(&run, style));
+        }
+    }
+    result
+}
+
+/// Rewrite the digits of one date or time part, such as a day or `s.00` in a duration
+///
+/// Padded parts are written digit by digit, like years; others with units.
+pub(crate) fn date_part(text: &str, padded: bool, style: DbNumStyle) -> String {
+    if padded {
+        return digits(text, style);
+    }
+    match text.split_once('.') {
+        Some((whole, fraction)) => format!("{}.{}", integer(whole, style), digits(fraction, style)),
+        None => integer(text, style),
+    }
+}
+
+/// Write each digit of a string as a numeral, leaving other characters unchanged
+pub(crate) fn digits(text: &str, style: DbNumStyle) -> String {
+    text.chars()
+        .map(|c| match c.to_digit(10) {
+            Some(d) => digit_char(d as usize, style),
+            None => c,
+        })
+        .collect()
+}
+
+/// Write a string of ASCII digits as a positional numeral
+///
+/// `[DBNum3]` has no units and falls back to full-width digits.
+pub(crate) fn integer(run: &str, style: DbNumStyle) -> String {
+    let units = match style {
+        DbNumStyle::DbNum1 => DBNUM1_UNITS,
+        DbNumStyle::DbNum2 => DBNUM2_UNITS,
+        DbNumStyle::DbNum3 => return digits(run, style),
+    };
+    let significant = run.trim_start_matches('0');
+    if significant.is_empty() {
+        return if run.is_empty() {
+            String::new()
+        } else {
+            digit_char(0, style).to_string()
+        };
+    }
+    // Beyond the largest group unit the digits are written one by one
+    if significant.len() > 4 * GROUP_UNITS.len() {
+        return digits(significant, style);
+    }
+
+    // Groups of four digits, most significant first, the first one left-padded
+    let values: Vec<usize> = significant
+        .chars()
+        .map(|c| c.to_digit(10).unwrap_or(0) as usize)
+        .collect();
+    let padding = (4 - values.len() % 4) % 4;
+    let padded: Vec<usize> = std::iter::repeat_n(0, padding).chain(values).collect();
+    let groups: Vec<&[usize]> = padded.chunks(4).collect();
+
+    let mut result = String::new();
+    // Any run of zeros between two non-zero digits is read as a single zero
+    let mut pending_zero = false;
+    for (i, group) in groups.iter().enumerate() {
+        if group.iter().all(|&d| d == 0) {
+            pending_zero = !result.is_empty();
+            continue;
+        }
+        for (j, &d) in group.iter().enumerate() {
+            let unit = 3 - j;
+            if d == 0 {
+                pending_zero |= !result.is_empty();
+                continue;
+            }
+            if pending_zero {
+                result.push(digit_char(0, style));
+                pending_zero = false;
+            }
+            result.push(digit_char(d, style));
+            if unit > 0 {
+                result.push(units[unit - 1]);
+            }
+        }
+        result.push_str(GROUP_UNITS[groups.len() - 1 - i]);
+    }
+
+    // 10 to 19 are read 十, 十一, ... without a leading one
+    if style == DbNumStyle::DbNum1 && result.starts_with("一十") {
+        result.remove(0);
+    }
+    result
+}
+
+fn digit_char(d: usize, style: DbNumStyle) -> char {
+    match style {
+        DbNumStyle::DbNum1 => DBNUM1_DIGITS[d],
+        DbNumStyle::DbNum2 => DBNUM2_DIGITS[d],
+        DbNumStyle::DbNum3 => char::from_u32('０' as u32 + d as u32).unwrap_or('０'),
+    }
+}
@@ -0,0 +1,296 @@
+//! Formatting of exact decimal numbers
+//!
+//! Number, percent and exponential sections are laid out from decimal digits,
+//! rounded exactly under the selected `RoundingMode`. An `f64` contributes the
+//! shortest decimal that reads back as the same double, so amounts beyond 2^53
+//! have already lost their last digits; `format_decimal` and `format_integer`
+//! take every digit from the caller instead. Fractions keep every digit of the
+//! whole number. Other sections, such as dates and General, see the nearest
+//! `f64`; General shows at most ten or so digits before switching to
+//! scientific notation, so it loses nothing it would show.
+
+use crate::formatter::{core, exponential, rounding, sections, standard_numeric};
+use crate::types::{
+    Decimal, FormatOptions, FormatSection, FormatToken, LocaleSettings, NumberFormat, RoundingMode,
+    ValueKind,
+};
+
+/// Format an exact decimal number
+///
+/// Number, percent, scaled and exponential sections are rounded half away
+/// from zero on the decimal digits, as Excel does with the value it shows.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_decimal;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{Decimal, LocaleSettings};
+///
+/// let format = parse_number_format("#,##0.00").unwrap();
+/// let locale = LocaleSettings::default();
+/// let amount: Decimal = "12345678901234567890.125".parse().unwrap();
+/// assert_eq!(
+///     format_decimal(&amount, &format, &locale),
+///     "12,345,678,901,234,567,890.13"
+/// );
+/// assert_eq!(format_decimal(&Decimal::new(1005, 3), &format, &locale), "1.01");
+/// ```
+pub fn format_decimal(value: &Decimal, format: &NumberFormat, locale: &LocaleSettings) -> String {
+    format_decimal_with_options(value, format, locale, &FormatOptions::default())
+}
+
+/// Format an exact decimal number with options such as the rounding mode
+///
+/// `excel_precision` only affects sections that see the nearest `f64`; the
+/// digits of a `Decimal` are shown as they are.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_decimal_with_options;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{Decimal, FormatOptions, LocaleSettings, RoundingMode};
+///
+/// let format = parse_number_format("0.00").unwrap();
+/// let options = FormatOptions::default().with_rounding_mode(RoundingMode::HalfEven);
+/// let value: Decimal = "2.345".parse().unwrap();
+/// let result = format_decimal_with_options(&value, &format, &LocaleSettings::default(), &options);
+/// assert_eq!(result, "2.34");
+/// ```
+pub fn format_decimal_with_options(
+    value: &Decimal,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    // Keep the sign of values too small for an f64 when choosing the section
+    let approximate = match value.to_f64() {
+        0.0 if !value.is_zero() && value.is_negative() => -f64::MIN_POSITIVE,
+        0.0 if !value.is_zero() => f64::MIN_POSITIVE,
+        approximate => approximate,
+    };
+    let section = sections::select_section(approximate, format);
+    if core::value_kind(section) == ValueKind::Fraction {
+        let (whole, fraction) = split_whole(&value.abs());
+        if let Some(result) = core::format_fraction_section(
+            value.is_negative(),
+            &whole,
+            fraction,
+            section,
+            locale,
+            options.rounding_mode,
+        ) {
+            return result;
+        }
+    }
+    if !core::is_placeholder_numeric(section) {
+        return super::format_number_with_options(approximate, format, locale, options);
+    }
+
+    // As for f64 values, a negative value without a negative section gets a minus sign
+    let is_fallback_for_negative = value.is_negative()
+        && format.negative_section.is_none()
+        && std::ptr::eq(section, &format.positive_section);
+
+    format_decimal_section(
+        value,
+        section,
+        locale,
+        is_fallback_for_negative,
+        options.rounding_mode,
+    )
+}
+
+/// Lay out an exact decimal on a number, percent or exponential section
+pub(super) fn format_decimal_section(
+    value: &Decimal,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    is_fallback_for_negative: bool,
+    rounding_mode: RoundingMode,
+) -> String {
+    let mut magnitude = value.abs();
+    if section.tokens.contains(&FormatToken::Percentage) {
+        magnitude = magnitude.shift(2);
+    }
+    magnitude = magnitude.shift(-3 * i32::from(section.num_scaling_commas));
+
+    if let Some(exp_token_idx) = section
+        .tokens
+        .iter()
+        .position(|t| matches!(t, FormatToken::Exponential(_)))
+    {
+        let places = exponential::mantissa_precision(section, exp_token_idx);
+        let multiple = exponential::exponent_multiple(section, exp_token_idx);
+        let (integer, decimals, exponent) = round_scientific(
+            &magnitude,
+            multiple,
+            places,
+            value.is_negative(),
+            rounding_mode,
+        );
+        exponential::write_exponential(
+            is_fallback_for_negative,
+            &integer,
+            &decimals,
+            exponent,
+            section,
+            exp_token_idx,
+            locale,
+        )
+    } else {
+        let places = standard_numeric::decimal_places(section);
+        let (integer, decimals) =
+            round_to_places(&magnitude, places, value.is_negative(), rounding_mode);
+        standard_numeric::render_standard_numeric(
+            value.is_negative(),
+            &integer,
+            &decimals,
+            magnitude.is_zero(),
+            section,
+            locale,
+            is_fallback_for_negative,
+        )
+    }
+}
+
+/// Format an integer exactly, whatever its size
+///
+/// Accepts `i64`, `u64`, `i128` and `u128`, so identifiers and counters
+/// beyond 2^53 keep every digit in number sections such as `#,##0` or
+/// `000-00-0000`, and in front of fractions such as `# ?/?`. Like
+/// `format_decimal`, dates and General see the nearest `f64`.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_integer;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::LocaleSettings;
+///
+/// let format = parse_number_format("#,##0").unwrap();
+/// let locale = LocaleSettings::default();
+/// assert_eq!(
+///     format_integer(u64::MAX, &format, &locale),
+///     "18,446,744,073,709,551,615"
+/// );
+/// ```
+pub fn format_integer<T: Into<Decimal>>(
+    value: T,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+) -> String {
+    format_decimal(&value.into(), format, locale)
+}
+
+/// Split a magnitude into the digits of its whole part and the fraction left over
+///
+/// Only the fraction goes through an `f64`, as fractions show a few digits of it.
+fn split_whole(value: &Decimal) -> (String, f64) {
+    let digits: String = value
+        .digits()
+        .iter()
+        .map(|d| char::from(b'0' + d))
+        .collect();
+    let exponent = i64::from(value.exponent());
+    if exponent >= 0 {
+        return (digits + &"0".repeat(exponent as usize), 0.0);
+    }
+    let split = (digits.len() as i64 + exponent).max(0) as usize;
+    let fraction_digits = format!(
+        "0.{:0>width$}",
+        &digits[split..],
+        width = (-exponent) as usize
+    );
+    let whole = match &digits[..split] {
+        "" => "0".to_string(),
+        whole => whole.to_string(),
+    };
+    (whole, fraction_digits.parse().unwrap_or(0.0))
+}
+
+/// Round a magnitude to `places` decimals
+///
+/// Returns the integer digits and one digit per decimal place.
+fn round_to_places(
+    value: &Decimal,
+    places: usize,
+    negative: bool,
+    mode: RoundingMode,
+) -> (String, Vec<u8>) {
+    // The digits of value × 10^places, rounded to an integer
+    let shift = i64::from(value.exponent()) + places as i64;
+    let mut scaled: Vec<u8> = value.digits().to_vec();
+    if shift >= 0 {
+        scaled.resize(scaled.len() + shift as usize, 0);
+    } else {
+        let kept = scaled.len() as i64 + shift;
+        // With no digit kept, the value is below half a unit unless the first digit is dropped
+        let remainder = if kept >= 0 {
+            rounding::digit_remainder(&scaled[kept as usize..])
+        } else {
+            rounding::Remainder::BelowHalf
+        };
+        scaled.truncate(kept.max(0) as usize);
+        let last_kept_odd = scaled.last().is_some_and(|d| d % 2 == 1);
+        if rounding::rounds_up(mode, negative, remainder, last_kept_odd) {
+            increment(&mut scaled);
+        }
+    }
+
+    if scaled.len() <= places {
+        let padding = places + 1 - scaled.len();
+        scaled.splice(0..0, std::iter::repeat_n(0, padding));
+    }
+    let (integer, decimals) = scaled.split_at(scaled.len() - places);
+    let leading_zeros = integer.iter().take_while(|d| **d == 0).count();
+    let integer: String = integer[leading_zeros.min(integer.len() - 1)..]
+        .iter()
+        .map(|d| char::from(b'0' + d))
+        .collect();
+    (integer, decimals.to_vec())
+}
+
+/// Round a magnitude for scientific notation with exponents that are multiples of `multiple`
+///
+/// Returns the integer digits of the mantissa, its `places` decimals and the
+/// exponent. A multiple of 0, as in `.00E+0`, keeps the mantissa below one.
+fn round_scientific(
+    value: &Decimal,
+    multiple: usize,
+    places: usize,
+    negative: bool,
+    mode: RoundingMode,
+) -> (String, Vec<u8>, i32) {
+    if value.is_zero() {
+        return ("0".to_string(), vec![0; places], 0);
+    }
+    let mut scientific = value.exponent() + value.digits().len() as i32 - 1;
+    loop {
+        let (exponent, integer_width) = if multiple == 0 {
+            (scientific + 1, 0)
+        } else {
+            let multiple = multiple as i32;
+            let exponent = scientific.div_euclid(multiple) * multiple;
+            (exponent, (scientific - exponent + 1) as usize)
+        };
+        let (integer, decimals) = round_to_places(&value.shift(-exponent), places, negative, mode);
+        // 9.99 rounding up to 10.0 needs the next exponent, so lay it out again
+        let width = if integer == "0" { 0 } else { integer.len() };
+        if width <= integer_width {
+            return (integer, decimals, exponent);
+        }
+        scientific += 1;
+    }
+}
+
+/// Add one to a number given as decimal digits, growing it on overflow
+fn increment(digits: &mut Vec<u8>) {
+    for digit in digits.iter_mut().rev() {
+        if *digit == 9 {
+            *digit = 0;
+        } else {
+            *digit += 1;
+            return;
+        }
+    }
+    digits.insert(0, 1);
+}
@@ -1,107 +1,169 @@
+use crate::formatter::dbnum;
 use crate::types::{ExponentialNotation, FormatSection, FormatToken, LocaleSettings};
 use std::fmt::Write;
+use std::ops::Range;
 
-/// Format a number in exponential notation
-pub(super) fn format_exponential(
-    value: f64,
+/// Number of digit placeholders between the decimal point and the exponent
+pub(super) fn mantissa_precision(section: &FormatSection, exp_token_idx: usize) -> usize {
+    section.tokens[..exp_token_idx]
+        .iter()
+        .skip_while(|token| !matches!(token, FormatToken::DecimalPoint))
+        .filter(|token| is_digit_placeholder(token))
+        .count()
+}
+
+/// Number of digit placeholders before the decimal point of the mantissa
+///
+/// Exponents are multiples of this count, so `##0.0E+0` gives engineering
+/// notation (12.3E+3) and `0.0E+0` one leading digit (1.2E+4). Only the count
+/// matters, not whether the placeholders are `0`, `#` or `?`: `#0.0E+0` and
+/// `00.0E+0` both use even exponents, and the `0`s only pad the mantissa, so
+/// `00.0E+0` shows 12345 as 01.2E+4.
+pub(super) fn exponent_multiple(section: &FormatSection, exp_token_idx: usize) -> usize {
+    section.tokens[..exp_token_idx]
+        .iter()
+        .take_while(|token| !matches!(token, FormatToken::DecimalPoint))
+        .filter(|token| is_digit_placeholder(token))
+        .count()
+}
+
+/// Lay out a rounded mantissa and its exponent on the tokens of the section
+///
+/// `integer` holds the integer digits of the mantissa and `decimals` one digit
+/// per placeholder after its decimal point. Digits of the mantissa and of the
+/// exponent fill their placeholders from the right: `0` pads with zeros, `?`
+/// with spaces and `#` not at all, so `0.0E+000` shows 1.2E+004. With
+/// `[DBNum1]` to `[DBNum3]`, the digits of the mantissa are written as numerals.
+pub(super) fn write_exponential(
+    show_minus: bool,
+    integer: &str,
+    decimals: &[u8],
+    exponent: i32,
     section: &FormatSection,
     exp_token_idx: usize,
     locale: &LocaleSettings,
 ) -> String {
     let mut result = String::new();
-
-    // Get the exponential token
-    let exp_token = &section.tokens[exp_token_idx];
-    let _exp_notation_type = match exp_token {
-        // Renamed to avoid unused var warning if only sign matters
-        FormatToken::Exponential(notation) => notation,
-        _ => unreachable!(), // Should be caught by caller
-    };
-
-    // Format with scientific notation
-    let abs_value = value.abs();
-    let (mantissa, exponent) = if abs_value == 0.0 {
-        (0.0, 0)
-    } else {
-        let log10_val = abs_value.log10();
-        let exponent_val = log10_val.floor();
-        let mantissa_val = abs_value / 10.0_f64.powf(exponent_val);
-        (mantissa_val, exponent_val as i32)
+    // Byte ranges of the text written by literal tokens
+    let mut literals: Vec<Range<usize>> = Vec::new();
+    let mut push_literal = |result: &mut String, text: &str| {
+        literals.push(result.len()..result.len() + text.len());
+        result.push_str(text);
     };
+    if show_minus {
+        result.push('-');
+    }
 
-    // Format mantissa part with proper precision
-    let is_negative = value < 0.0;
-    let sign = if is_negative { "-" } else { "" };
+    // A zero integer part shows only where a `0` or `?` placeholder asks for it
+    let integer = if integer == "0" { "" } else { integer };
+    let integer_placeholders = exponent_multiple(section, exp_token_idx);
+    let mut integer_digits = integer.chars();
+    let mut integer_idx = 0;
+    let mut decimal_idx = 0;
+    let mut in_decimals = false;
 
-    // Count number of desired decimal places in mantissa
-    let mut mantissa_precision = 0; // Default to 0, meaning only the integer part of mantissa if no frac part in format
-    let mut in_mantissa_decimal_part = false;
-    for token in section.tokens.iter().take(exp_token_idx) {
-        if matches!(token, FormatToken::DecimalPoint) {
-            in_mantissa_decimal_part = true;
-            continue;
-        }
-        if in_mantissa_decimal_part
-            && matches!(
-                token,
-                FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
-            )
-        {
-            mantissa_precision += 1;
+    let mut idx = 0;
+    while idx < section.tokens.len() {
+        let token = &section.tokens[idx];
+        match token {
+            FormatToken::Exponential(notation) => {
+                let sign = if exponent < 0 {
+                    "-"
+                } else {
+                    match notation {
+                        ExponentialNotation::Plus => "+",
+                        ExponentialNotation::Minus => "",
+                    }
+                };
+                write!(result, "E{}", sign).unwrap();
+                let exponent_placeholders: Vec<&FormatToken> = section.tokens[idx + 1..]
+                    .iter()
+                    .take_while(|t| is_digit_placeholder(t))
+                    .collect();
+                let digits = exponent.unsigned_abs().to_string();
+                write_integer_digits(&mut result, &digits, &exponent_placeholders);
+                idx += exponent_placeholders.len();
+            }
+            _ if idx > exp_token_idx => match token {
+                FormatToken::LiteralChar(c) => {
+                    push_literal(&mut result, c.encode_utf8(&mut [0; 4]))
+                }
+                FormatToken::QuotedText(text) => push_literal(&mut result, text),
+                _ => {}
+            },
+            FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+                if in_decimals =>
+            {
+                let digit = decimals.get(decimal_idx).copied().unwrap_or(0);
+                // Only trailing zeros of the mantissa are optional
+                let trailing_zero = decimals[decimal_idx.min(decimals.len())..]
+                    .iter()
+                    .all(|d| *d == 0);
+                match token {
+                    FormatToken::DigitIfNeeded if trailing_zero => {}
+                    FormatToken::DigitOrSpace if trailing_zero => result.push(' '),
+                    _ => result.push(char::from(b'0' + digit)),
+                }
+                decimal_idx += 1;
+            }
+            FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace => {
+                // Digits beyond the placeholders go to the first one
+                let padding = integer_placeholders as isize - integer.len() as isize;
+                if integer_idx == 0 && padding < 0 {
+                    for _ in 0..-padding {
+                        result.extend(integer_digits.next());
+                    }
+                }
+                if (integer_idx as isize) < padding {
+                    match token {
+                        FormatToken::DigitOrZero => result.push('0'),
+                        FormatToken::DigitOrSpace => result.push(' '),
+                        _ => {}
+                    }
+                } else {
+                    result.extend(integer_digits.next());
+                }
+                integer_idx += 1;
+            }
+            FormatToken::DecimalPoint => {
+                result.push(locale.decimal_point);
+                in_decimals = true;
+            }
+            FormatToken::LiteralChar(c) => push_literal(&mut result, c.encode_utf8(&mut [0; 4])),
+            FormatToken::QuotedText(text) => push_literal(&mut result, text),
+            FormatToken::Percentage => result.push('%'),
+            FormatToken::CurrencySymbolLocaleDefault => {
+                push_literal(&mut result, &locale.currency_symbol)
+            }
+            FormatToken::SkipWidth(_) => result.push(' '),
+            _ => {}
         }
+        idx += 1;
     }
-    // If no decimal point was found before E, check if there are integer placeholders before E
-    // Excel default: For "0E+00", 12345 -> 1E+04 (no decimals in mantissa)
-    // For "0.00E+00", 12345 -> 1.23E+04 (2 decimals in mantissa)
-    // So, mantissa_precision calculation above based on tokens after decimal point and before E is correct.
-    // If mantissa_precision is still 0, it means format like "0E+00".
-
-    // Round mantissa correctly based on its desired precision
-    let power = 10.0_f64.powi(mantissa_precision as i32);
-    let rounded_mantissa = (mantissa * power).round() / power;
-
-    // Adjust exponent if rounding mantissa caused it to become >= 10 or < 1
-    let (final_mantissa, final_exponent) = if rounded_mantissa == 0.0 {
-        // handle 0.0 case separately
-        (0.0, 0)
-    } else if rounded_mantissa >= 10.0 {
-        (rounded_mantissa / 10.0, exponent + 1)
-    } else if rounded_mantissa < 1.0 && mantissa != 0.0 {
-        // mantissa != 0.0 to avoid 0.0 becoming 0.0 E-1
-        // This case needs care: if format is 0E+00, and value is 0.123 -> 1E-01
-        // if format is 0.0E+00, and value is 0.0123 -> 1.2E-02
-        // The initial mantissa calculation (abs_value / 10.0_f64.powf(exponent_val)) ensures mantissa >=1 and <10
-        // So, rounding alone should not make it < 1 unless original value was very small and precision is low.
-        // If it does become < 1 due to rounding (e.g. 1.0000xxx rounded to 0 precision -> 1.0, but if 0.5 rounded to 0 precision -> 1.0. If 0.4 -> 0.0)
-        // Let's stick to initial mantissa/exponent adjustment and rely on precision for rounding.
-        (rounded_mantissa, exponent) // Revisit if exponent adjustment for mantissa < 1 due to rounding is needed
-    } else {
-        (rounded_mantissa, exponent)
-    };
-
-    write!(result, "{}", sign).unwrap();
-
-    let mut mantissa_str = format!(
-        "{:.precision$}",
-        final_mantissa,
-        precision = mantissa_precision
-    );
-    if locale.decimal_point != '.' {
-        mantissa_str = mantissa_str.replace('.', &locale.decimal_point.to_string());
+    match dbnum::section_dbnum(section) {
+        Some(style) => dbnum::convert_placeholder_digits(&result, &literals, style, locale),
+        None => result,
     }
-    write!(result, "{}", mantissa_str).unwrap();
+}
 
-    // Add E notation
-    let final_exp_sign_str = if final_exponent < 0 {
-        "-"
-    } else {
-        match &section.tokens[exp_token_idx] {
-            FormatToken::Exponential(ExponentialNotation::Plus) => "+",
-            FormatToken::Exponential(ExponentialNotation::Minus) => "",
-            _ => unreachable!(), // Should be caught by caller or parser
+/// Right-align digits on placeholders, always showing at least one digit
+fn write_integer_digits(result: &mut String, digits: &str, placeholders: &[&FormatToken]) {
+    for placeholder in placeholders
+        .iter()
+        .take(placeholders.len().saturating_sub(digits.len()))
+    {
+        match placeholder {
+            FormatToken::DigitOrZero => result.push('0'),
+            FormatToken::DigitOrSpace => result.push(' '),
+            _ => {}
         }
-    };
-    write!(result, "E{}{:02}", final_exp_sign_str, final_exponent.abs()).unwrap();
+    }
+    result.push_str(digits);
+}
 
-    result
+fn is_digit_placeholder(token: &FormatToken) -> bool {
+    matches!(
+        token,
+        FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+    )
 }
@@ -1,6 +1,7 @@
 use super::placeholder_handler::format_integer_like_segment;
 use super::utils;
-use crate::types::{FormatSection, FormatToken, LocaleSettings};
+use crate::formatter::rounding;
+use crate::types::{FormatSection, FormatToken, LocaleSettings, RoundingMode};
 
 #[derive(Debug, Default, Clone)]
 pub struct FractionPatternAnalysis {
@@ -131,10 +132,15 @@ pub fn analyze_fraction_pattern(section: &FormatSection) -> FractionPatternAnaly
     analysis
 }
 
+/// Format a magnitude as a whole number and a fraction
+///
+/// `whole` holds the digits of the whole number and `fraction` what is left,
+/// from 0 up to 1, so whole numbers of any size keep all their digits.
 #[allow(clippy::too_many_arguments)]
 pub fn format_number_as_fraction(
-    original_value_for_sign: f64,
-    value_for_formatting_placeholders: f64,
+    negative: bool,
+    whole: &str,
+    fraction: f64,
     _locale: &LocaleSettings,
     integer_part_tokens: &[FormatToken],
     numerator_tokens: &[FormatToken],
@@ -142,20 +148,17 @@ pub fn format_number_as_fraction(
     fixed_denominator_value: Option<u32>,
     has_explicit_slash: bool,
     section_has_only_placeholders: bool,
+    rounding_mode: RoundingMode,
 ) -> Option<String> {
-    let abs_value = value_for_formatting_placeholders;
-    let integer_part_val_f = abs_value.trunc();
-    let mut decimal_part = abs_value.fract();
-    if decimal_part < 0.0 {
-        decimal_part = decimal_part.abs();
-    }
+    let decimal_part = fraction.abs();
 
     let (mut num_val, den_val): (i64, i64) = if let Some(fixed_den) = fixed_denominator_value {
         if fixed_den == 0 {
             return None;
         }
         (
-            (decimal_part * fixed_den as f64).round() as i64,
+            rounding::round_magnitude(decimal_part * fixed_den as f64, negative, rounding_mode)
+                as i64,
             fixed_den as i64,
         )
     } else {
@@ -163,29 +166,24 @@ pub fn format_number_as_fraction(
             return None;
         }
         let max_den_precision = denominator_tokens.len().max(1);
-        utils::decimal_to_fraction(decimal_part, max_den_precision)?
+        match rounding::directed(rounding_mode, negative) {
+            Some(round_up) => utils::directed_fraction(decimal_part, max_den_precision, round_up)?,
+            None => utils::decimal_to_fraction(decimal_part, max_den_precision)?,
+        }
     };
 
-    let mut final_integer_val_i64 = integer_part_val_f as i64;
-    if num_val == den_val && den_val != 0 {
-        if abs_value >= 0.0 {
-            final_integer_val_i64 += 1;
-        } else {
-            final_integer_val_i64 -= 1;
-        }
-        num_val = 0;
-    } else if num_val > den_val && den_val != 0 {
-        if abs_value >= 0.0 {
-            final_integer_val_i64 += num_val / den_val;
-        } else {
-            final_integer_val_i64 -= num_val / den_val;
+    // A fraction rounded up to a whole carries into the whole number
+    let mut int_digits_str = whole.to_string();
+    if num_val >= den_val && den_val != 0 {
+        for _ in 0..num_val / den_val {
+            utils::increment_digits(&mut int_digits_str);
         }
         num_val %= den_val;
     }
 
-    let show_leading_sign = original_value_for_sign < 0.0;
-    let int_digits_str = final_integer_val_i64.abs().to_string();
-    let int_segment_is_effectively_zero = final_integer_val_i64 == 0;
+    let show_leading_sign = negative;
+    let whole_is_zero = int_digits_str.bytes().all(|b| b == b'0');
+    let int_segment_is_effectively_zero = whole_is_zero;
 
     let mut int_part_formatted = if integer_part_tokens.is_empty() {
         String::new()
@@ -198,13 +196,13 @@ pub fn format_number_as_fraction(
     };
 
     let mut display_int_part = !int_part_formatted.is_empty()
-        || (final_integer_val_i64 == 0
+        || (whole_is_zero
             && num_val == 0
             && integer_part_tokens
                 .iter()
                 .any(|t| matches!(t, FormatToken::DigitOrZero)));
 
-    if final_integer_val_i64 == 0
+    if whole_is_zero
         && num_val != 0
         && integer_part_tokens.len() == 1
         && matches!(integer_part_tokens[0], FormatToken::DigitOrZero)
@@ -227,7 +225,7 @@ pub fn format_number_as_fraction(
         only_hash_and_spaces_in_int_tokens = true;
     }
 
-    if final_integer_val_i64 == 0
+    if whole_is_zero
         && num_val == 0
         && only_hash_and_spaces_in_int_tokens
         && section_has_only_placeholders
@@ -236,7 +234,7 @@ pub fn format_number_as_fraction(
         display_int_part = true;
     }
 
-    if final_integer_val_i64 == 0
+    if whole_is_zero
         && integer_part_tokens.len() == 1
         && matches!(integer_part_tokens[0], FormatToken::DigitOrZero)
         && section_has_only_placeholders
@@ -256,7 +254,8 @@ pub fn format_number_as_fraction(
     let mut result_str;
 
     let mut force_display_fraction_as_zero_denom = false;
-    if value_for_formatting_placeholders == 0.0
+    if whole_is_zero
+        && decimal_part == 0.0
         && section_has_only_placeholders
         && integer_part_tokens.is_empty()
         && (fixed_denominator_value.is_some() || !denominator_tokens.is_empty())
@@ -310,7 +309,7 @@ pub fn format_number_as_fraction(
             }
             parts.push(formatted_numerator);
         } else {
-            if final_integer_val_i64 == 0
+            if whole_is_zero
                 && integer_part_tokens
                     .iter()
                     .any(|t| matches!(t, FormatToken::DigitIfNeeded))
@@ -354,7 +353,7 @@ pub fn format_number_as_fraction(
     {
         result_str.insert(0, '-');
     }
-    if result_str.is_empty() && final_integer_val_i64 == 0 && num_val == 0 {
+    if result_str.is_empty() && whole_is_zero && num_val == 0 {
         return Some("0".to_string());
     }
 
@@ -165,6 +165,57 @@ pub(super) fn decimal_to_fraction(
     }
 }
 
+/// The closest fraction on one side of a decimal, for modes that round in one direction
+///
+/// Looks at every denominator within the digit limit and keeps the closest
+/// fraction not above `decimal`, or not below it with `round_up`, preferring
+/// the smaller denominator on ties. Returns (0, 1) for 0.0.
+pub(super) fn directed_fraction(
+    decimal: f64,
+    max_denominator_digits: usize,
+    round_up: bool,
+) -> Option<(i64, i64)> {
+    if decimal == 0.0 {
+        return Some((0, 1));
+    }
+    if max_denominator_digits == 0 {
+        return None;
+    }
+    let max_denominator = 10_i64.pow(max_denominator_digits.min(9) as u32) - 1;
+
+    let mut best = (i64::from(round_up), 1);
+    let mut best_error = f64::INFINITY;
+    for denominator in 1..=max_denominator {
+        let scaled = decimal * denominator as f64;
+        // Float noise just past a whole numerator is not a remainder
+        let numerator = if round_up {
+            (scaled - 1e-9).ceil()
+        } else {
+            (scaled + 1e-9).floor()
+        };
+        let error = (numerator / denominator as f64 - decimal).abs();
+        if error < best_error - 1e-12 {
+            best = (numerator as i64, denominator);
+            best_error = error;
+        }
+        if error < 1e-12 {
+            break;
+        }
+    }
+    Some(best)
+}
+
+/// Add one to a whole number given as decimal digits, growing it on overflow
+pub(super) fn increment_digits(digits: &mut String) {
+    let nines = digits.bytes().rev().take_while(|b| *b == b'9').count();
+    let kept = digits.len() - nines;
+    let next = match digits[..kept].bytes().last() {
+        Some(last) => format!("{}{}", &digits[..kept - 1], char::from(last + 1)),
+        None => "1".to_string(),
+    };
+    *digits = next + &"0".repeat(nines);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
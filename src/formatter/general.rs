@@ -0,0 +1,136 @@
+//! General format handling module
+//!
+//! This module renders the `General` token, either on its own or surrounded by
+//! literals such as in `General" units"` or `[Blue]-General`.
+
+use crate::formatter::dbnum;
+use crate::types::{FormatSection, FormatToken, LocaleSettings};
+
+/// Format a value with a section containing the `General` token
+///
+/// With `[DBNum1]` to `[DBNum3]`, only the digits of the value are written as numerals.
+pub fn format_general_section(
+    value: f64,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+) -> String {
+    let dbnum_style = dbnum::section_dbnum(section);
+    let mut result = String::new();
+    for token in &section.tokens {
+        match token {
+            FormatToken::GeneralNumeric => {
+                let text = format_general(value);
+                match dbnum_style {
+                    Some(style) => {
+                        result.push_str(&dbnum::convert_number_text(&text, style, locale))
+                    }
+                    None => result.push_str(&text),
+                }
+            }
+            FormatToken::LiteralChar(c) => result.push(*c),
+            FormatToken::QuotedText(text) => result.push_str(text),
+            FormatToken::CurrencySymbolLocaleDefault => result.push_str(&locale.currency_symbol),
+            FormatToken::CurrencySymbolLocalePrefixed(value) => {
+                // Stored as "<prefix>:<locale code>"
+                let prefix = value.split_once(':').map_or(value.as_str(), |(p, _)| p);
+                result.push_str(prefix);
+            }
+            FormatToken::SkipWidth(_) => result.push(' '),
+            _ => {}
+        }
+    }
+    result
+}
+
+/// Format a value the way Excel's General format does
+pub fn format_general(value: f64) -> String {
+    if value.is_nan() {
+        return "NaN".to_string(); // Consider locale for NaN if needed
+    }
+    if value.is_infinite() {
+        return if value.is_sign_positive() {
+            "Infinity"
+        } else {
+            "-Infinity"
+        }
+        .to_string();
+    }
+    if value == 0.0 {
+        return "0".to_string();
+    }
+
+    let abs_val = value.abs();
+    let mut s_val;
+
+    // Determine if scientific notation is needed
+    // Excel uses scientific for abs(value) >= 1E11 or (abs(value) < 1E-4 and non-zero)
+    // These thresholds are approximate and can depend on context/Excel version.
+    let use_scientific = abs_val >= 1E11 || (abs_val < 1E-4 && abs_val != 0.0);
+
+    if use_scientific {
+        // Format as X.YYYYYYE+ZZ (approx. 6-7 decimal places for mantissa)
+        s_val = format!("{:.6E}", value);
+        // Ensure E is uppercase and exponent is two digits with sign
+        if let Some(e_pos) = s_val.find('e').or_else(|| s_val.find('E')) {
+            let (mantissa, mut exponent_part) = s_val.split_at(e_pos);
+            exponent_part = exponent_part
+                .trim_start_matches('E')
+                .trim_start_matches('e');
+            let sign = if exponent_part.starts_with('-') {
+                '-'
+            } else {
+                '+'
+            };
+            let num_str = exponent_part.trim_start_matches(['+', '-']);
+            if let Ok(num) = num_str.parse::<i32>() {
+                s_val = format!("{}E{}{:02}", mantissa, sign, num.abs());
+            } else {
+                // Fallback if exponent parsing fails, just ensure E is uppercase
+                s_val = s_val.replace('e', "E");
+            }
+        } else {
+            // Should not happen if format! worked, but as a fallback
+            s_val = value.to_string().replace('e', "E");
+        }
+    } else {
+        s_val = value.to_string();
+        // For non-scientific, f64::to_string() is generally good.
+        // It removes trailing .0 for whole numbers.
+        // Targetting around 10 significant digits for General format.
+
+        // If s_val contains a decimal point and its total length is too long for ~10 sig digits.
+        let effective_len_check = if value < 0.0 { 12 } else { 11 };
+
+        if s_val.contains('.')
+            && s_val.len() > effective_len_check
+            && let Some((full_int_part, frac_part)) = s_val.split_once('.')
+        {
+            let (sign_prefix, numeric_int_part_str) =
+                if let Some(stripped) = full_int_part.strip_prefix('-') {
+                    ("-", stripped)
+                } else {
+                    ("", full_int_part)
+                };
+
+            let numeric_int_digits_count = numeric_int_part_str.len();
+
+            let allowed_frac_digits = 10_usize.saturating_sub(numeric_int_digits_count);
+
+            if frac_part.len() > allowed_frac_digits {
+                if allowed_frac_digits == 0 {
+                    s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
+                } else {
+                    s_val = format!(
+                        "{}{}.{}",
+                        sign_prefix,
+                        numeric_int_part_str,
+                        &frac_part[..allowed_frac_digits]
+                    );
+                }
+            } else if allowed_frac_digits == 0 && !frac_part.is_empty() {
+                s_val = format!("{}{}", sign_prefix, numeric_int_part_str);
+            }
+        }
+    }
+    s_val
+}
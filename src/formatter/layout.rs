@@ -0,0 +1,221 @@
+//! Column-width layout module
+//!
+//! `format_number` ignores the `*` fill token and renders `_x` as a single
+//! space. For fixed-width output such as text exports and terminal tables, this
+//! module repeats the fill character until the text reaches the column width
+//! and pads `_x` by the display width of `x`.
+
+use crate::types::{
+    FormatOptions, FormatSection, FormatToken, LocaleSettings, NumberFormat, SelectedSection,
+};
+
+/// Stands in for the fill token while the section is formatted
+const FILL_MARKER: char = '\u{E000}';
+
+/// Format a number for a column of the given width in character cells
+///
+/// The first `*x` of the selected section is expanded with `x` so that the
+/// result is `width` cells wide; further fill tokens are dropped. When the text
+/// is already as wide as the column, or the section has no fill, the text is
+/// returned without padding.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_number_with_width;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::LocaleSettings;
+///
+/// let format = parse_number_format("_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_)").unwrap();
+/// let locale = LocaleSettings::default();
+/// assert_eq!(format_number_with_width(1234.5, &format, &locale, 14), " $   1,234.50 ");
+/// assert_eq!(format_number_with_width(-1234.5, &format, &locale, 14), " $  (1,234.50)");
+/// ```
+pub fn format_number_with_width(
+    value: f64,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+) -> String {
+    format_number_with_width_and_options(value, format, locale, width, &FormatOptions::default())
+}
+
+/// Format a number for a column of the given width with options such as the date system
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_number_with_width_and_options;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{FormatOptions, LocaleSettings, RoundingMode};
+///
+/// let format = parse_number_format("* 0.0").unwrap();
+/// let options = FormatOptions::default().with_rounding_mode(RoundingMode::TowardZero);
+/// let result =
+///     format_number_with_width_and_options(1.99, &format, &LocaleSettings::default(), 6, &options);
+/// assert_eq!(result, "   1.9");
+/// ```
+pub fn format_number_with_width_and_options(
+    value: f64,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+    options: &FormatOptions,
+) -> String {
+    let result =
+        super::format_number_rich_with_options(value, &layout_format(format), locale, options);
+    expand_fill(
+        &result.text,
+        selected_section(format, result.section),
+        width,
+    )
+}
+
+/// Format a text value for a column of the given width
+///
+/// The section that displays the text, as chosen by `format_text`, is laid out
+/// like a number section.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_text_with_width;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::LocaleSettings;
+///
+/// let format = parse_number_format("_(0_);(0);0;_(*.@_)").unwrap();
+/// let result = format_text_with_width("abc", &format, &LocaleSettings::default(), 8);
+/// assert_eq!(result, " ...abc ");
+/// ```
+pub fn format_text_with_width(
+    text: &str,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+) -> String {
+    let result = super::format_text_rich(text, &layout_format(format), locale);
+    expand_fill(
+        &result.text,
+        selected_section(format, result.section),
+        width,
+    )
+}
+
+/// The format with every section prepared for layout
+fn layout_format(format: &NumberFormat) -> NumberFormat {
+    NumberFormat {
+        positive_section: layout_section(&format.positive_section),
+        negative_section: format.negative_section.as_ref().map(layout_section),
+        zero_section: format.zero_section.as_ref().map(layout_section),
+        text_section: format.text_section.as_ref().map(layout_section),
+    }
+}
+
+/// The section of the original format that was used
+fn selected_section(format: &NumberFormat, section: SelectedSection) -> Option<&FormatSection> {
+    match section {
+        SelectedSection::Positive | SelectedSection::Conditional(0) => {
+            Some(&format.positive_section)
+        }
+        SelectedSection::Negative | SelectedSection::Conditional(1) => {
+            format.negative_section.as_ref()
+        }
+        SelectedSection::Zero | SelectedSection::Conditional(_) => format.zero_section.as_ref(),
+        SelectedSection::Text => format.text_section.as_ref(),
+    }
+}
+
+/// Replace fill tokens with a marker and widen `_x` to the display width of `x`
+fn layout_section(section: &FormatSection) -> FormatSection {
+    let mut tokens = Vec::with_capacity(section.tokens.len());
+    for token in &section.tokens {
+        match token {
+            FormatToken::Fill(_) => tokens.push(FormatToken::LiteralChar(FILL_MARKER)),
+            FormatToken::SkipWidth(c) => {
+                for _ in 0..char_width(*c) {
+                    tokens.push(FormatToken::SkipWidth(' '));
+                }
+            }
+            _ => tokens.push(token.clone()),
+        }
+    }
+    FormatSection {
+        tokens,
+        ..section.clone()
+    }
+}
+
+/// Expand the first fill marker so the text is `width` cells wide, and drop the others
+///
+/// Fraction sections show no literals, so the marker does not survive them; the
+/// fill then goes before the text if it comes before the first placeholder and
+/// after it otherwise.
+fn expand_fill(text: &str, section: Option<&FormatSection>, width: usize) -> String {
+    let Some((fill, fill_index)) = section.and_then(|s| {
+        s.tokens.iter().enumerate().find_map(|(i, t)| match t {
+            FormatToken::Fill(c) => Some((*c, i)),
+            _ => None,
+        })
+    }) else {
+        return text.replace(FILL_MARKER, "");
+    };
+    let content_width: usize = text
+        .chars()
+        .filter(|c| *c != FILL_MARKER)
+        .map(char_width)
+        .sum();
+    let fill_count = match char_width(fill) {
+        0 => 0,
+        fill_width => width.saturating_sub(content_width) / fill_width,
+    };
+    let padding: String = std::iter::repeat_n(fill, fill_count).collect();
+
+    if !text.contains(FILL_MARKER) {
+        let fill_leads = section.is_some_and(|s| {
+            !s.tokens[..fill_index].iter().any(|t| {
+                matches!(
+                    t,
+                    FormatToken::DigitOrZero
+                        | FormatToken::DigitIfNeeded
+                        | FormatToken::DigitOrSpace
+                )
+            })
+        });
+        return if fill_leads {
+            padding + text
+        } else {
+            text.to_string() + &padding
+        };
+    }
+
+    let mut result = String::with_capacity(text.len() + padding.len());
+    let mut expanded = false;
+    for c in text.chars() {
+        if c != FILL_MARKER {
+            result.push(c);
+        } else if !expanded {
+            expanded = true;
+            result.push_str(&padding);
+        }
+    }
+    result
+}
+
+/// Number of character cells a character occupies
+///
+/// East Asian wide and fullwidth characters take two cells, combining marks none.
+pub fn char_width(c: char) -> usize {
+    match c as u32 {
+        0x0300..=0x036F | 0x200B..=0x200F | 0xFE00..=0xFE0F => 0,
+        0x1100..=0x115F
+        | 0x2E80..=0x303E
+        | 0x3041..=0x33FF
+        | 0x3400..=0x4DBF
+        | 0x4E00..=0x9FFF
+        | 0xA000..=0xA4CF
+        | 0xAC00..=0xD7A3
+        | 0xF900..=0xFAFF
+        | 0xFE30..=0xFE4F
+        | 0xFF00..=0xFF60
+        | 0xFFE0..=0xFFE6
+        | 0x20000..=0x3FFFD => 2,
+        _ => 1,
+    }
+}
@@ -6,17 +6,33 @@
 //!
 //! This module implements formatting of numbers according to parsed number format patterns.
 
-use crate::types::{FormatSection, LocaleSettings, NumberFormat};
+use crate::types::{
+    CellValue, ColorType, FormatOptions, FormatSection, FormatToken, FormattedValue,
+    LocaleSettings, NumberFormat, SelectedSection,
+};
 
 pub mod core;
 pub mod datetime;
+mod dbnum;
+pub mod decimal;
 pub mod empty_section;
 pub mod exponential;
 pub mod fraction;
+pub mod general;
+pub mod layout;
+mod rounding;
 pub mod sections;
 pub mod standard_numeric;
+pub mod temporal;
 pub mod text;
 
+pub use decimal::{format_decimal, format_decimal_with_options, format_integer};
+pub use layout::{
+    format_number_with_width, format_number_with_width_and_options, format_text_with_width,
+};
+pub use temporal::format_temporal;
+pub use text::{format_text, format_text_rich};
+
 /// Format a number according to the specified number format pattern
 ///
 /// # Arguments
@@ -38,11 +54,117 @@ pub mod text;
 /// assert_eq!(result, "123.46");
 /// ```
 pub fn format_number(value: f64, format: &NumberFormat, locale: &LocaleSettings) -> String {
-    // Handle special cases first: text value
-    if value.is_nan() && format.text_section.is_some() {
-        return text::format_text("NaN", format, locale);
+    format_number_rich_with_options(value, format, locale, &FormatOptions::default()).text
+}
+
+/// Format a number with options such as the date system
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_number_with_options;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{DateSystem, FormatOptions, LocaleSettings};
+///
+/// let format = parse_number_format("yyyy-mm-dd").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// let result = format_number_with_options(0.0, &format, &LocaleSettings::default(), &options);
+/// assert_eq!(result, "1904-01-01");
+/// ```
+pub fn format_number_with_options(
+    value: f64,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    format_number_rich_with_options(value, format, locale, options).text
+}
+
+/// Format a cell value the way Excel displays it
+///
+/// Numbers use the number sections and text the text section (see `format_text`).
+/// Booleans are shown with the locale's names and errors as they are, whatever
+/// the format; empty cells are shown empty.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_value;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{CellValue, LocaleSettings};
+///
+/// let format = parse_number_format("0.00;-0.00;0;\"[\"@\"]\"").unwrap();
+/// let locale = LocaleSettings::default();
+/// assert_eq!(format_value(&CellValue::Number(1.5), &format, &locale), "1.50");
+/// assert_eq!(format_value(&CellValue::Text("x".into()), &format, &locale), "[x]");
+/// assert_eq!(format_value(&CellValue::Bool(true), &format, &locale), "TRUE");
+/// assert_eq!(format_value(&CellValue::Error("#N/A".into()), &format, &locale), "#N/A");
+/// ```
+pub fn format_value(value: &CellValue, format: &NumberFormat, locale: &LocaleSettings) -> String {
+    format_value_with_options(value, format, locale, &FormatOptions::default())
+}
+
+/// Format a cell value with options such as the date system
+///
+/// The options apply to numbers; text, booleans and errors are shown as with `format_value`.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_value_with_options;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{CellValue, DateSystem, FormatOptions, LocaleSettings};
+///
+/// let format = parse_number_format("yyyy-mm-dd").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// let result =
+///     format_value_with_options(&CellValue::Number(0.0), &format, &LocaleSettings::default(), &options);
+/// assert_eq!(result, "1904-01-01");
+/// ```
+pub fn format_value_with_options(
+    value: &CellValue,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    match value {
+        CellValue::Number(number) => format_number_with_options(*number, format, locale, options),
+        CellValue::Text(text) => format_text(text, format, locale),
+        CellValue::Bool(true) => locale.boolean_names[0].clone(),
+        CellValue::Bool(false) => locale.boolean_names[1].clone(),
+        CellValue::Error(error) => error.clone(),
+        CellValue::Empty => String::new(),
     }
+}
 
+/// Format a number and report the color, section and interpretation used
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_number_rich;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{ColorType, LocaleSettings, SelectedSection, ValueKind};
+///
+/// let format = parse_number_format("#,##0;[Red]-#,##0").unwrap();
+/// let result = format_number_rich(-1234.0, &format, &LocaleSettings::default());
+/// assert_eq!(result.text, "-1,234");
+/// assert_eq!(result.color, Some(ColorType::Red));
+/// assert_eq!(result.section, SelectedSection::Negative);
+/// assert_eq!(result.value_kind, ValueKind::Number);
+/// ```
+pub fn format_number_rich(
+    value: f64,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+) -> FormattedValue {
+    format_number_rich_with_options(value, format, locale, &FormatOptions::default())
+}
+
+/// Format a number with options and report the color, section and interpretation used
+pub fn format_number_rich_with_options(
+    value: f64,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> FormattedValue {
+    let section_index = sections::select_section_index(value, format);
     let section_to_use = sections::select_section(value, format);
 
     let original_value_for_sign_check = value;
@@ -79,11 +201,41 @@ pub fn format_number(value: f64, format: &NumberFormat, locale: &LocaleSettings)
     // If value is 0.0 and zero_section is picked, or value is positive and positive_section is picked,
     // is_fallback_for_negative remains false, and value_for_formatting_placeholders is the original value.
 
-    core::format_value(
+    let text = core::format_value(
         original_value_for_sign_check,
         value_for_formatting_placeholders,
         section_to_use,
         locale,
         is_fallback_for_negative,
-    )
+        options,
+    );
+
+    let section = if section_to_use.condition.is_some() {
+        SelectedSection::Conditional(section_index)
+    } else {
+        match section_index {
+            1 => SelectedSection::Negative,
+            2 => SelectedSection::Zero,
+            _ => SelectedSection::Positive,
+        }
+    };
+
+    let value_kind = core::value_kind(section_to_use);
+
+    FormattedValue {
+        text,
+        color: section_color(section_to_use),
+        section,
+        value_kind,
+    }
+}
+
+/// The section's color, which may also appear after other tokens
+fn section_color(section: &FormatSection) -> Option<ColorType> {
+    section.color.clone().or_else(|| {
+        section.tokens.iter().find_map(|token| match token {
+            FormatToken::Color(color) => Some(color.clone()),
+            _ => None,
+        })
+    })
 }
@@ -0,0 +1,92 @@
+//! Rounding to the digits a section shows, under the selected `RoundingMode`
+//!
+//! Every path rounds a magnitude and knows the sign of the value separately:
+//! the sign only matters for `Floor` and `Ceiling`, which round the magnitude
+//! of negative values the other way.
+
+use crate::types::RoundingMode;
+
+/// Tolerance for float noise when rounding `f64` magnitudes
+const EPSILON: f64 = 1e-9;
+
+/// How the dropped part of a magnitude compares with half a unit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Remainder {
+    Zero,
+    BelowHalf,
+    Half,
+    AboveHalf,
+}
+
+/// The direction a mode rounds any remainder in, or `None` for the half modes
+///
+/// `Some(true)` means up in magnitude.
+pub(crate) fn directed(mode: RoundingMode, negative: bool) -> Option<bool> {
+    match mode {
+        RoundingMode::TowardZero => Some(false),
+        RoundingMode::AwayFromZero => Some(true),
+        RoundingMode::Floor => Some(negative),
+        RoundingMode::Ceiling => Some(!negative),
+        RoundingMode::HalfUp | RoundingMode::HalfEven | RoundingMode::HalfDown => None,
+    }
+}
+
+/// Whether a magnitude moves up to the next unit
+///
+/// `last_kept_odd` tells `HalfEven` whether the kept part ends in an odd digit.
+pub(crate) fn rounds_up(
+    mode: RoundingMode,
+    negative: bool,
+    remainder: Remainder,
+    last_kept_odd: bool,
+) -> bool {
+    if remainder == Remainder::Zero {
+        return false;
+    }
+    if let Some(up) = directed(mode, negative) {
+        return up;
+    }
+    match mode {
+        RoundingMode::HalfEven => {
+            remainder == Remainder::AboveHalf || (remainder == Remainder::Half && last_kept_odd)
+        }
+        RoundingMode::HalfDown => remainder == Remainder::AboveHalf,
+        _ => remainder != Remainder::BelowHalf,
+    }
+}
+
+/// Compare dropped decimal digits, most significant first, with half a unit
+pub(crate) fn digit_remainder(dropped: &[u8]) -> Remainder {
+    let rest_is_zero = dropped.iter().skip(1).all(|d| *d == 0);
+    match dropped.first() {
+        None | Some(0) if rest_is_zero => Remainder::Zero,
+        Some(5) if rest_is_zero => Remainder::Half,
+        Some(d) if *d >= 5 => Remainder::AboveHalf,
+        _ => Remainder::BelowHalf,
+    }
+}
+
+/// Round a non-negative `f64` to a whole number
+pub(crate) fn round_magnitude(magnitude: f64, negative: bool, mode: RoundingMode) -> f64 {
+    let whole = magnitude.floor();
+    let fraction = magnitude - whole;
+    // Values a hair away from a whole number or a half are taken to be on it
+    if fraction < EPSILON {
+        return whole;
+    }
+    if fraction > 1.0 - EPSILON {
+        return whole + 1.0;
+    }
+    let remainder = if (fraction - 0.5).abs() < EPSILON {
+        Remainder::Half
+    } else if fraction < 0.5 {
+        Remainder::BelowHalf
+    } else {
+        Remainder::AboveHalf
+    };
+    if rounds_up(mode, negative, remainder, whole % 2.0 == 1.0) {
+        whole + 1.0
+    } else {
+        whole
+    }
+}
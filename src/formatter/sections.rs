@@ -1,73 +1,53 @@
-use crate::types::{ComparisonOperator, FormatSection, NumberFormat};
+use crate::types::{FormatSection, NumberFormat};
 
 /// Select the appropriate format section based on the value and format conditions
 pub(super) fn select_section(value: f64, format: &NumberFormat) -> &FormatSection {
-    // Check for conditional sections first
-    if let Some(condition) = &format.positive_section.condition {
-        let matches = match condition.operator {
-            ComparisonOperator::Eq => value == condition.value,
-            ComparisonOperator::Gt => value > condition.value,
-            ComparisonOperator::Lt => value < condition.value,
-            ComparisonOperator::Ge => value >= condition.value,
-            ComparisonOperator::Le => value <= condition.value,
-            ComparisonOperator::Ne => value != condition.value,
-        };
-
-        if matches {
-            return &format.positive_section;
-        }
+    match select_section_index(value, format) {
+        1 => format.negative_section.as_ref(),
+        2 => format.zero_section.as_ref(),
+        _ => None,
     }
+    .unwrap_or(&format.positive_section)
+}
 
-    if let Some(section) = &format.negative_section {
-        if let Some(condition) = &section.condition {
-            let matches = match condition.operator {
-                ComparisonOperator::Eq => value == condition.value,
-                ComparisonOperator::Gt => value > condition.value,
-                ComparisonOperator::Lt => value < condition.value,
-                ComparisonOperator::Ge => value >= condition.value,
-                ComparisonOperator::Le => value <= condition.value,
-                ComparisonOperator::Ne => value != condition.value,
-            };
-
-            if matches {
-                return section;
-            }
-        }
+/// Index of the section used for the value (0 = positive, 1 = negative, 2 = zero)
+pub(crate) fn select_section_index(value: f64, format: &NumberFormat) -> usize {
+    // Check for conditional sections first
+    if let Some(condition) = &format.positive_section.condition
+        && condition.matches(value)
+    {
+        return 0;
     }
 
-    if let Some(section) = &format.zero_section {
-        if let Some(condition) = &section.condition {
-            let matches = match condition.operator {
-                ComparisonOperator::Eq => value == condition.value,
-                ComparisonOperator::Gt => value > condition.value,
-                ComparisonOperator::Lt => value < condition.value,
-                ComparisonOperator::Ge => value >= condition.value,
-                ComparisonOperator::Le => value <= condition.value,
-                ComparisonOperator::Ne => value != condition.value,
-            };
+    if let Some(section) = &format.negative_section
+        && let Some(condition) = &section.condition
+        && condition.matches(value)
+    {
+        return 1;
+    }
 
-            if matches {
-                return section;
-            }
-        }
+    if let Some(section) = &format.zero_section
+        && let Some(condition) = &section.condition
+        && condition.matches(value)
+    {
+        return 2;
     }
 
     // If no conditions matched or no conditional sections defined,
     // use standard sign-based selection
     if value < 0.0 {
-        if let Some(section) = &format.negative_section {
-            if section.condition.is_none() {
-                return section;
-            }
-        }
-    } else if value == 0.0 {
-        if let Some(section) = &format.zero_section {
-            if section.condition.is_none() {
-                return section;
-            }
+        if let Some(section) = &format.negative_section
+            && section.condition.is_none()
+        {
+            return 1;
         }
+    } else if value == 0.0
+        && let Some(section) = &format.zero_section
+        && section.condition.is_none()
+    {
+        return 2;
     }
 
     // Default to positive section
-    &format.positive_section
+    0
 }
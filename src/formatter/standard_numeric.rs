@@ -1,85 +1,45 @@
+use crate::formatter::dbnum;
 use crate::types::{FormatSection, FormatToken, LocaleSettings};
 use std::fmt::Write;
+use std::ops::Range;
 
-#[allow(clippy::too_many_arguments)]
-pub(super) fn format_standard_numeric_core(
-    original_value_for_sign: f64,
-    adjusted_value: f64, // Value after abs(), percentage, scaling
+/// Number of digit placeholders after the decimal point
+pub(super) fn decimal_places(section: &FormatSection) -> usize {
+    section
+        .tokens
+        .iter()
+        .skip_while(|token| !matches!(token, FormatToken::DecimalPoint))
+        .filter(|token| {
+            matches!(
+                token,
+                FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+            )
+        })
+        .count()
+}
+
+/// Lay out rounded digits on the placeholders and literals of the section
+///
+/// `integer_str` holds the integer digits without sign or grouping, and
+/// `decimal_digits_vec` one digit per placeholder after the decimal point.
+/// With `[DBNum1]` to `[DBNum3]`, the digits the placeholders show are
+/// written as numerals and literal text is kept as is.
+pub(super) fn render_standard_numeric(
+    is_negative: bool,
+    integer_str: &str,
+    decimal_digits_vec: &[u8],
+    is_zero: bool,
     section: &FormatSection,
     locale: &LocaleSettings,
     is_positive_section_fallback_for_negative: bool,
 ) -> String {
-    const EPSILON: f64 = 1e-9;
     let mut result = String::new();
-
-    let mut local_decimal_places = 0;
-    let mut after_decimal_flag = false;
-    for token in &section.tokens {
-        if after_decimal_flag
-            && matches!(
-                token,
-                FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
-            )
-        {
-            local_decimal_places += 1;
-        } else if matches!(token, FormatToken::DecimalPoint) {
-            after_decimal_flag = true;
-        }
-    }
-
-    let initial_integer_part_val = adjusted_value.trunc();
-    let mut initial_decimal_part = adjusted_value.fract();
-
-    if initial_decimal_part < 0.0 {
-        initial_decimal_part = -initial_decimal_part;
-    }
-    if adjusted_value.fract().abs() < EPSILON || initial_integer_part_val == adjusted_value {
-        initial_decimal_part = 0.0;
-    }
-
-    let mut decimal_digits_vec: Vec<u8> = Vec::with_capacity(local_decimal_places);
-    let mut temp_decimal_part_for_extraction = initial_decimal_part;
-
-    if local_decimal_places > 0 {
-        for _ in 0..local_decimal_places {
-            temp_decimal_part_for_extraction *= 10.0;
-            let digit = temp_decimal_part_for_extraction.trunc() as u8;
-            decimal_digits_vec.push(digit.min(9));
-            temp_decimal_part_for_extraction -= temp_decimal_part_for_extraction.trunc();
-        }
-    }
-    let final_remaining_decimal = temp_decimal_part_for_extraction.abs();
-
-    let integer_to_format: i64;
-    if local_decimal_places == 0 {
-        integer_to_format = adjusted_value.round() as i64;
-        decimal_digits_vec.clear();
-    } else {
-        let mut current_integer_part_intermediate = initial_integer_part_val as i64;
-        if final_remaining_decimal >= (0.5 - EPSILON) {
-            let mut carry = true;
-            for i in (0..decimal_digits_vec.len()).rev() {
-                if !carry {
-                    break;
-                }
-                decimal_digits_vec[i] += 1;
-                if decimal_digits_vec[i] == 10 {
-                    decimal_digits_vec[i] = 0;
-                    if i == 0 {
-                        current_integer_part_intermediate += 1;
-                    }
-                } else {
-                    carry = false;
-                }
-            }
-        }
-        integer_to_format = current_integer_part_intermediate;
-    }
-
-    let integer_str = integer_to_format.to_string();
+    // Byte ranges of the text written by literal tokens
+    let mut literals: Vec<Range<usize>> = Vec::new();
+    let integer_is_zero = integer_str.bytes().all(|b| b == b'0');
+    let after_decimal_flag = section.tokens.contains(&FormatToken::DecimalPoint);
     let int_digits: Vec<char> = integer_str.chars().collect();
 
-    let is_negative = original_value_for_sign < 0.0;
     let uses_parentheses = section.tokens.iter().any(|t| {
         matches!(t, FormatToken::LiteralChar('(')) || matches!(t, FormatToken::LiteralChar(')'))
     });
@@ -90,7 +50,7 @@ pub(super) fn format_standard_numeric_core(
         .any(|token| matches!(token, FormatToken::ThousandsSeparator));
 
     let mut formatted_integer_part_vec: Vec<char>;
-    if should_apply_thousands_separator && !int_digits.is_empty() && integer_to_format != 0 {
+    if should_apply_thousands_separator && !int_digits.is_empty() && !integer_is_zero {
         formatted_integer_part_vec =
             Vec::with_capacity(int_digits.len() + (int_digits.len() - 1) / 3);
         if !(int_digits.len() == 1 && int_digits[0] == '0') {
@@ -130,7 +90,7 @@ pub(super) fn format_standard_numeric_core(
         }
     }
 
-    let num_actual_raw_int_digits = if integer_to_format == 0 && total_integer_placeholders > 0 {
+    let num_actual_raw_int_digits = if integer_is_zero && total_integer_placeholders > 0 {
         1
     } else {
         int_digits.len()
@@ -160,11 +120,10 @@ pub(super) fn format_standard_numeric_core(
                 }
 
                 if !sign_printed && literal_is_acting_as_sign {
-                    result.push(*c);
                     sign_printed = true;
-                } else {
-                    result.push(*c);
                 }
+                literals.push(result.len()..result.len() + c.len_utf8());
+                result.push(*c);
             }
             FormatToken::QuotedText(text) => {
                 while int_digits_iter.peek().is_some()
@@ -184,10 +143,11 @@ pub(super) fn format_standard_numeric_core(
                 {
                     sign_printed = true;
                 }
+                literals.push(result.len()..result.len() + text.len());
                 result.push_str(text);
             }
             FormatToken::DecimalPoint => {
-                if !actual_int_digit_printed && integer_to_format == 0 {
+                if !actual_int_digit_printed && integer_is_zero {
                     let has_mandatory_int_zero_placeholder = section
                         .tokens
                         .iter()
@@ -208,6 +168,22 @@ pub(super) fn format_standard_numeric_core(
             }
             FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace => {
                 if !in_decimal_part {
+                    // Digits beyond the placeholders go to the first one, so that
+                    // 1234567890123 with 000-00-0000 shows 1234567-89-0123
+                    if current_int_placeholder_idx == 0
+                        && num_actual_raw_int_digits > total_integer_placeholders
+                    {
+                        let mut overflow = num_actual_raw_int_digits - total_integer_placeholders;
+                        while let Some(c) = int_digits_iter
+                            .next_if(|c| overflow > 0 || *c == locale.thousands_separator)
+                        {
+                            if c.is_ascii_digit() {
+                                overflow -= 1;
+                            }
+                            result.push(c);
+                            actual_int_digit_printed = true;
+                        }
+                    }
                     let mut char_to_print: Option<char> = None;
                     let mut consumed_digit_this_turn = false;
                     if current_int_placeholder_idx < padding_len {
@@ -227,9 +203,8 @@ pub(super) fn format_standard_numeric_core(
                             FormatToken::DigitIfNeeded => {
                                 if actual_int_digit_printed
                                     || digit_char != '0'
-                                    || (num_actual_raw_int_digits == 1 && integer_to_format == 0)
-                                    || (integer_to_format == 0
-                                        && int_digits_iter.clone().count() == 1)
+                                    || (num_actual_raw_int_digits == 1 && integer_is_zero)
+                                    || (integer_is_zero && int_digits_iter.clone().count() == 1)
                                 {
                                     char_to_print = Some(digit_char);
                                 }
@@ -301,7 +276,7 @@ pub(super) fn format_standard_numeric_core(
                     result.push(int_digits_iter.next().unwrap());
                     actual_int_digit_printed = true;
                 }
-                if !actual_int_digit_printed && integer_to_format == 0 {
+                if !actual_int_digit_printed && integer_is_zero {
                     result.push('0');
                     actual_int_digit_printed = true;
                 }
@@ -310,9 +285,11 @@ pub(super) fn format_standard_numeric_core(
             FormatToken::ThousandsSeparator => {}
             FormatToken::TextValue => {}
             FormatToken::CurrencySymbolLocaleDefault => {
+                literals.push(result.len()..result.len() + locale.currency_symbol.len());
                 result.push_str(&locale.currency_symbol);
             }
             FormatToken::CurrencySymbolLocalePrefixed(value) => {
+                let start = result.len();
                 // Parse the combined value (prefix:locale_code)
                 if let Some((prefix, locale_code)) = value.split_once(':') {
                     // Try to get locale-specific settings
@@ -328,6 +305,7 @@ pub(super) fn format_standard_numeric_core(
                     // Simple case - just use the value directly
                     result.push_str(value);
                 }
+                literals.push(start..result.len());
             }
             FormatToken::SkipWidth(_) => {
                 result.push(' ');
@@ -342,7 +320,7 @@ pub(super) fn format_standard_numeric_core(
     }
 
     if !actual_int_digit_printed
-        && adjusted_value == 0.0
+        && is_zero
         && result
             .chars()
             .all(|c| c.is_whitespace() || c == '(' || c == ')')
@@ -362,7 +340,7 @@ pub(super) fn format_standard_numeric_core(
                     .any(|t| matches!(t, FormatToken::DigitOrSpace))
             {
                 result.push('0');
-            } else if result.trim().is_empty() && integer_to_format == 0 && !after_decimal_flag {
+            } else if result.trim().is_empty() && integer_is_zero && !after_decimal_flag {
                 let has_mandatory_zero_placeholder = section
                     .tokens
                     .iter()
@@ -374,6 +352,10 @@ pub(super) fn format_standard_numeric_core(
         }
     }
 
+    if let Some(style) = dbnum::section_dbnum(section) {
+        result = dbnum::convert_placeholder_digits(&result, &literals, style, locale);
+    }
+
     if is_negative {
         if uses_parentheses {
             if !sign_printed {
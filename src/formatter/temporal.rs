@@ -0,0 +1,83 @@
+//! Formatting of chrono values
+//!
+//! Spreadsheet libraries usually hand dates over as chrono values. Converting
+//! them to an `f64` serial first loses sub-microsecond digits for present-day
+//! dates, so date and duration sections format these values directly; other
+//! sections, such as `0.00`, see the serial number Excel would store.
+
+use chrono::NaiveDateTime;
+
+use crate::formatter::{datetime, sections};
+use crate::types::{FormatOptions, FormatToken, LocaleSettings, NumberFormat, TemporalValue};
+
+/// Format a chrono date, time or duration
+///
+/// The section is chosen by the value's serial number as for `format_number`.
+/// Dates that Excel cannot store are shown as a run of `#`, like serials out
+/// of range.
+///
+/// # Examples
+/// ```
+/// use chrono::NaiveDate;
+/// use number_format::formatter::format_temporal;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{FormatOptions, LocaleSettings, TemporalValue};
+///
+/// let dt = NaiveDate::from_ymd_opt(2024, 5, 6)
+///     .unwrap()
+///     .and_hms_nano_opt(7, 8, 9, 123_456_789)
+///     .unwrap();
+/// let format = parse_number_format("yyyy-mm-dd hh:mm:ss.000").unwrap();
+/// let result = format_temporal(
+///     &TemporalValue::from(dt),
+///     &format,
+///     &LocaleSettings::default(),
+///     &FormatOptions::default(),
+/// );
+/// assert_eq!(result, "2024-05-06 07:08:09.123");
+/// ```
+pub fn format_temporal(
+    value: &TemporalValue,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> String {
+    let Some(serial) = value.to_excel_serial(options.date_system) else {
+        let section = sections::select_section(1.0, format);
+        return datetime::invalid_date_placeholder(section, locale, options);
+    };
+
+    let section = sections::select_section(serial, format);
+    let is_general = section.tokens.contains(&FormatToken::GeneralNumeric);
+    let is_duration = datetime::section_is_duration(section);
+    let is_datetime = datetime::section_is_datetime_point_in_time(section);
+
+    match value {
+        TemporalValue::Duration(duration) if is_duration && !is_general && serial >= 0.0 => {
+            datetime::format_duration_exact(*duration, section)
+        }
+        TemporalValue::Duration(_) => {
+            super::format_number_with_options(serial, format, locale, options)
+        }
+        _ if is_datetime && !is_duration && !is_general => {
+            match to_naive_datetime(value, options) {
+                Some(dt) => datetime::format_naive_datetime(&dt, section, locale),
+                None => super::format_number_with_options(serial, format, locale, options),
+            }
+        }
+        _ => super::format_number_with_options(serial, format, locale, options),
+    }
+}
+
+/// The date and time a point-in-time value shows, with a bare time on serial day 0
+fn to_naive_datetime(value: &TemporalValue, options: &FormatOptions) -> Option<NaiveDateTime> {
+    match value {
+        TemporalValue::Date(date) => Some(date.and_time(chrono::NaiveTime::MIN)),
+        TemporalValue::DateTime(dt) => Some(*dt),
+        TemporalValue::Time(time) => {
+            let day_zero = datetime::convert_f64_to_datetime(0.0, options.date_system)?;
+            Some(day_zero.date().and_time(*time))
+        }
+        TemporalValue::Duration(_) => None,
+    }
+}
@@ -1,7 +1,14 @@
-use crate::types::{FormatSection, FormatToken, LocaleSettings, NumberFormat};
+use crate::types::{
+    FormatSection, FormatToken, FormattedValue, LocaleSettings, NumberFormat, SelectedSection,
+    ValueKind,
+};
 
 /// Format a text value according to the specified number format pattern
 ///
+/// Follows Excel: the fourth section is used if present; a format with a single
+/// section containing `@` is applied to text as well; otherwise the text is
+/// shown unchanged.
+///
 /// # Arguments
 /// * `text` - The text value to format
 /// * `format` - The parsed number format pattern
@@ -9,13 +16,78 @@ use crate::types::{FormatSection, FormatToken, LocaleSettings, NumberFormat};
 ///
 /// # Returns
 /// * `String` - The formatted text string
-pub(super) fn format_text(text: &str, format: &NumberFormat, locale: &LocaleSettings) -> String {
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_text;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::LocaleSettings;
+///
+/// let locale = LocaleSettings::default();
+/// let format = parse_number_format("\"Name: \"@").unwrap();
+/// assert_eq!(format_text("Ada", &format, &locale), "Name: Ada");
+///
+/// let format = parse_number_format("0.00").unwrap();
+/// assert_eq!(format_text("Ada", &format, &locale), "Ada");
+/// ```
+pub fn format_text(text: &str, format: &NumberFormat, locale: &LocaleSettings) -> String {
+    format_text_rich(text, format, locale).text
+}
+
+/// Format a text value and report the color and section used
+///
+/// The section is `Positive` when a single `@` section displays the text, and
+/// `Text` otherwise, including when no section applies and the text is shown
+/// unchanged.
+///
+/// # Examples
+/// ```
+/// use number_format::formatter::format_text_rich;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{ColorType, LocaleSettings, SelectedSection};
+///
+/// let format = parse_number_format("0;-0;0;[Blue]\"<\"@\">\"").unwrap();
+/// let result = format_text_rich("Ada", &format, &LocaleSettings::default());
+/// assert_eq!(result.text, "<Ada>");
+/// assert_eq!(result.color, Some(ColorType::Blue));
+/// assert_eq!(result.section, SelectedSection::Text);
+/// ```
+pub fn format_text_rich(
+    text: &str,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+) -> FormattedValue {
+    let Some(section) = text_section_for(format) else {
+        // If no section applies to text, return the text as is
+        return FormattedValue {
+            text: text.to_string(),
+            color: None,
+            section: SelectedSection::Text,
+            value_kind: ValueKind::Text,
+        };
+    };
+    FormattedValue {
+        text: format_text_with_section(text, section, locale),
+        color: super::section_color(section),
+        section: if format.text_section.is_some() {
+            SelectedSection::Text
+        } else {
+            SelectedSection::Positive
+        },
+        value_kind: ValueKind::Text,
+    }
+}
+
+/// The section used to display text, if any
+fn text_section_for(format: &NumberFormat) -> Option<&FormatSection> {
     if let Some(text_section) = &format.text_section {
-        format_text_with_section(text, text_section, locale)
-    } else {
-        // If no text section is defined, return the text as is
-        text.to_string()
+        return Some(text_section);
+    }
+    let is_single_section = format.negative_section.is_none() && format.zero_section.is_none();
+    if is_single_section && format.positive_section.has_text_format {
+        return Some(&format.positive_section);
     }
+    None
 }
 
 /// Format a text value with a text section
@@ -28,7 +100,8 @@ pub(super) fn format_text_with_section(
 
     for token in &section.tokens {
         match token {
-            FormatToken::TextValue => {
+            // General in a text section shows the text unchanged
+            FormatToken::TextValue | FormatToken::GeneralNumeric => {
                 result.push_str(text_to_insert);
             }
             FormatToken::LiteralChar(c) => {
@@ -37,6 +110,9 @@ pub(super) fn format_text_with_section(
             FormatToken::QuotedText(quoted_text) => {
                 result.push_str(quoted_text);
             }
+            FormatToken::SkipWidth(_) => {
+                result.push(' ');
+            }
             FormatToken::CurrencySymbolLocaleDefault => {
                 result.push_str(&locale.currency_symbol);
             }
@@ -58,7 +134,7 @@ pub(super) fn format_text_with_section(
                 }
             }
             _ => {
-                // Ignore other tokens like Fill, SkipWidth, numeric/date placeholders in text section
+                // Ignore other tokens like Fill, numeric/date placeholders in text section
             }
         }
     }
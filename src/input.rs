@@ -0,0 +1,762 @@
+//! Parsing of displayed text back into a value
+//!
+//! `parse_value` is the inverse of `format_number` for data entry: it walks
+//! the tokens of each number section against the input, the way the formatter
+//! walks them to produce output, and returns the number or date serial the text
+//! stands for. Matching is lenient about whitespace and letter case, and about
+//! placeholders that would have printed nothing.
+
+use std::fmt;
+
+use chrono::{NaiveDate, NaiveTime};
+
+use crate::formatter::datetime;
+use crate::formatter::fraction::analyze_fraction_pattern;
+use crate::types::{
+    CalendarType, FormatOptions, FormatSection, FormatToken, LocaleSettings, NumberFormat,
+};
+
+/// Error returned when text cannot be read back with a format
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputError {
+    /// The input is empty or only whitespace
+    Empty,
+    /// No section matches the input; `position` is the byte offset where the
+    /// section that matched the longest prefix stopped matching
+    Mismatch { position: usize },
+    /// A date or time section matches, but the fields name no valid date or
+    /// time, such as `31.02.2024` or `13:00 PM`
+    InvalidDate,
+    /// No section can be read back, such as a text-only format or one with
+    /// `[DBNum1]` numerals, era years, extended tokens or a non-Gregorian calendar
+    Unsupported,
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "Input is empty"),
+            InputError::Mismatch { position } => {
+                write!(f, "Input does not match the format at byte {}", position)
+            }
+            InputError::InvalidDate => write!(f, "Input is not a valid date or time"),
+            InputError::Unsupported => write!(f, "Format cannot be used to read values"),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+/// Read the value a formatted text stands for
+///
+/// The positive, negative and zero sections are tried in order and the first
+/// one that matches the whole input wins. Text matched by the negative section
+/// gives a negative number, as does a leading `-` elsewhere; sections with a
+/// condition only match values that satisfy it. Dates are returned as serial
+/// numbers in the 1900 date system, durations as fractions of a day; use
+/// `parse_value_with_options` for the 1904 date system.
+///
+/// # Examples
+/// ```
+/// use number_format::input::parse_value;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::LocaleSettings;
+///
+/// let locale = LocaleSettings::default();
+/// let format = parse_number_format("#,##0.00;(#,##0.00)").unwrap();
+/// assert_eq!(parse_value("(1,234.50)", &format, &locale), Ok(-1234.5));
+///
+/// let format = parse_number_format("dd.mm.yyyy").unwrap();
+/// assert_eq!(parse_value("12.03.2024", &format, &locale), Ok(45363.0));
+/// ```
+pub fn parse_value(
+    input: &str,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+) -> Result<f64, InputError> {
+    parse_value_with_options(input, format, locale, &FormatOptions::default())
+}
+
+/// Read the value a formatted text stands for, with the options it was formatted with
+///
+/// Dates are returned as serial numbers in `options.date_system`. With
+/// `options.proleptic_dates`, dates before the epoch give negative serials
+/// instead of `InputError::InvalidDate`.
+///
+/// # Examples
+/// ```
+/// use number_format::input::parse_value_with_options;
+/// use number_format::parser::parse_number_format;
+/// use number_format::types::{DateSystem, FormatOptions, LocaleSettings};
+///
+/// let locale = LocaleSettings::default();
+/// let format = parse_number_format("yyyy-mm-dd").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// assert_eq!(
+///     parse_value_with_options("1904-01-02", &format, &locale, &options),
+///     Ok(1.0)
+/// );
+/// ```
+pub fn parse_value_with_options(
+    input: &str,
+    format: &NumberFormat,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> Result<f64, InputError> {
+    if input.trim().is_empty() {
+        return Err(InputError::Empty);
+    }
+
+    let sections = [
+        Some(&format.positive_section),
+        format.negative_section.as_ref(),
+        format.zero_section.as_ref(),
+    ];
+    // As in `select_section`, the sign only picks the section when no section has a condition
+    let has_conditions = sections
+        .iter()
+        .flatten()
+        .any(|section| section.condition.is_some());
+    let mut best_error = InputError::Unsupported;
+    for (index, section) in sections.into_iter().enumerate() {
+        let Some(section) = section else {
+            continue;
+        };
+        // Only a negative section picked by sign shows the value without its sign
+        let negative_by_sign = index == 1 && !has_conditions;
+        match parse_section(input, section, locale, options, !negative_by_sign) {
+            Ok(value) => {
+                let value = if negative_by_sign { -value } else { value };
+                match &section.condition {
+                    Some(condition) if !condition.matches(value) => {
+                        best_error = closer_error(best_error, InputError::Mismatch { position: 0 });
+                    }
+                    _ => return Ok(value),
+                }
+            }
+            Err(error) => best_error = closer_error(best_error, error),
+        }
+    }
+    Err(best_error)
+}
+
+/// The more informative of two errors from different sections
+fn closer_error(current: InputError, new: InputError) -> InputError {
+    fn rank(error: &InputError) -> (u8, usize) {
+        match error {
+            InputError::Unsupported | InputError::Empty => (0, 0),
+            InputError::Mismatch { position } => (1, *position),
+            InputError::InvalidDate => (2, 0),
+        }
+    }
+    if rank(&new) > rank(&current) {
+        new
+    } else {
+        current
+    }
+}
+
+/// Match the input against one section
+fn parse_section(
+    input: &str,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+    allow_sign: bool,
+) -> Result<f64, InputError> {
+    if section.tokens.is_empty() || section.has_text_format && !has_general(section) {
+        return Err(InputError::Unsupported);
+    }
+    if section.tokens.iter().any(|token| {
+        matches!(
+            token,
+            FormatToken::DbNum(_)
+                | FormatToken::MonthLetter
+                | FormatToken::EraNameShort
+                | FormatToken::EraNameAbbr
+                | FormatToken::EraNameFull
+                | FormatToken::EraYear
+                | FormatToken::EraYearPadded
+                | FormatToken::Quarter
+                | FormatToken::QuarterName
+                | FormatToken::IsoWeek
+                | FormatToken::IsoWeekYear
+                | FormatToken::DayOfYear
+                | FormatToken::DayOfYearPadded
+        )
+    }) {
+        return Err(InputError::Unsupported);
+    }
+
+    let mut cursor = Cursor::new(input);
+    let negative = allow_sign && cursor.eat_char('-');
+    let value = if !has_general(section)
+        && (datetime::section_is_duration(section)
+            || datetime::section_is_datetime_point_in_time(section))
+    {
+        if datetime::section_calendar(section) != CalendarType::Gregorian {
+            return Err(InputError::Unsupported);
+        }
+        let tag_locale = datetime::section_locale(section);
+        parse_datetime(
+            &mut cursor,
+            section,
+            tag_locale.as_ref().unwrap_or(locale),
+            options,
+        )?
+    } else {
+        parse_numeric(&mut cursor, section, locale)?
+    };
+
+    if !cursor.is_at_end() {
+        return Err(cursor.mismatch());
+    }
+    Ok(if negative { -value } else { value })
+}
+
+fn has_general(section: &FormatSection) -> bool {
+    section.tokens.contains(&FormatToken::GeneralNumeric)
+}
+
+/// Where the digits of a number section currently go
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum NumberPart {
+    Integer,
+    Fraction,
+    Exponent,
+    Denominator,
+}
+
+/// Match a number, percent, scaled, exponential, fraction or General section
+fn parse_numeric(
+    cursor: &mut Cursor,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+) -> Result<f64, InputError> {
+    let is_fraction = analyze_fraction_pattern(section).is_fraction_format;
+    let mut integer_runs: Vec<String> = Vec::new();
+    let mut fraction = String::new();
+    let mut denominator = String::new();
+    let mut exponent: i32 = 0;
+    let mut percent_count = 0;
+    let mut part = NumberPart::Integer;
+    let mut in_run = false;
+
+    let tokens = &section.tokens;
+    for (index, token) in tokens.iter().enumerate() {
+        let continues_run = in_run;
+        in_run = false;
+        match token {
+            FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace => {
+                in_run = true;
+                // A run of placeholders reads all its digits at its first placeholder
+                if continues_run || part == NumberPart::Exponent {
+                    continue;
+                }
+                let allow_spaces = tokens[index..]
+                    .iter()
+                    .take_while(|t| is_placeholder(t) || **t == FormatToken::ThousandsSeparator)
+                    .any(|t| *t == FormatToken::DigitOrSpace);
+                let grouping = (part == NumberPart::Integer && !is_fraction)
+                    .then_some(locale.thousands_separator);
+                let run = cursor.digit_run(grouping, allow_spaces);
+                match part {
+                    NumberPart::Integer => integer_runs.push(run),
+                    NumberPart::Fraction => fraction.push_str(&run),
+                    NumberPart::Denominator => denominator.push_str(&run),
+                    NumberPart::Exponent => {}
+                }
+            }
+            FormatToken::ThousandsSeparator => in_run = continues_run,
+            FormatToken::DecimalPoint => {
+                // "5." may be typed as "5"
+                cursor.eat_char(locale.decimal_point);
+                part = NumberPart::Fraction;
+            }
+            FormatToken::Percentage => {
+                cursor.skip_spaces();
+                if !cursor.eat_char('%') {
+                    return Err(cursor.mismatch());
+                }
+                percent_count += 1;
+            }
+            FormatToken::Exponential(_) => {
+                if !(cursor.eat_char('E') || cursor.eat_char('e')) {
+                    return Err(cursor.mismatch());
+                }
+                exponent = cursor.signed_integer().ok_or_else(|| cursor.mismatch())?;
+                part = NumberPart::Exponent;
+            }
+            FormatToken::GeneralNumeric => {
+                let (whole, decimals, power) = cursor
+                    .general_number(locale)
+                    .ok_or_else(|| cursor.mismatch())?;
+                integer_runs.push(whole);
+                fraction.push_str(&decimals);
+                exponent = power;
+            }
+            FormatToken::LiteralChar('/') if is_fraction && part == NumberPart::Integer => {
+                // A whole number is shown without its fraction part
+                if cursor.is_at_end() {
+                    break;
+                }
+                if !cursor.eat_char('/') {
+                    return Err(cursor.mismatch());
+                }
+                part = NumberPart::Denominator;
+            }
+            _ => match_literal(cursor, token, locale)?,
+        }
+    }
+
+    let scale = 3 * i32::from(section.num_scaling_commas) - 2 * percent_count;
+    if is_fraction {
+        let denominator = match section.fixed_denominator {
+            Some(value) if denominator.is_empty() => u64::from(value),
+            _ => denominator.parse::<u64>().unwrap_or(0),
+        };
+        // Without a whole number, " 3/4" reads the numerator at the whole-number placeholders
+        let mut runs = integer_runs.iter().filter(|run| !run.is_empty());
+        let (whole, numerator) = if part == NumberPart::Denominator {
+            let numerator = runs.next_back().map_or("", String::as_str);
+            (runs.next_back().map_or("", String::as_str), numerator)
+        } else {
+            (runs.next().map_or("", String::as_str), "")
+        };
+        let mut value = decimal_value(whole, "", scale).ok_or_else(|| cursor.mismatch())?;
+        if !numerator.is_empty() {
+            if denominator == 0 {
+                return Err(cursor.mismatch());
+            }
+            let numerator = decimal_value(numerator, "", scale).ok_or_else(|| cursor.mismatch())?;
+            value += numerator / denominator as f64;
+        }
+        return Ok(value);
+    }
+
+    decimal_value(&integer_runs.concat(), &fraction, exponent + scale)
+        .ok_or_else(|| cursor.mismatch())
+}
+
+/// The value of `integer.fraction × 10^exponent`, rounded once
+///
+/// Returns `None` when the value is too large for an `f64`.
+fn decimal_value(integer: &str, fraction: &str, exponent: i32) -> Option<f64> {
+    let integer = if integer.is_empty() { "0" } else { integer };
+    let fraction = if fraction.is_empty() { "0" } else { fraction };
+    let value: f64 = format!("{}.{}e{}", integer, fraction, exponent)
+        .parse()
+        .unwrap_or(0.0);
+    value.is_finite().then_some(value)
+}
+
+fn is_placeholder(token: &FormatToken) -> bool {
+    matches!(
+        token,
+        FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+    )
+}
+
+/// Match a token that shows the same text whatever the value
+///
+/// Tokens that show nothing, such as colors and locale tags, match nothing.
+fn match_literal(
+    cursor: &mut Cursor,
+    token: &FormatToken,
+    locale: &LocaleSettings,
+) -> Result<(), InputError> {
+    let text = match token {
+        FormatToken::LiteralChar(c) => c.to_string(),
+        FormatToken::QuotedText(text) => text.clone(),
+        FormatToken::CurrencySymbolLocaleDefault => locale.currency_symbol.clone(),
+        FormatToken::CurrencySymbolLocalePrefixed(value) => match value.split_once(':') {
+            Some((prefix, _)) => prefix.to_string(),
+            None => value.clone(),
+        },
+        // `_x` and `*x` are rendered as at most some spaces
+        FormatToken::SkipWidth(_) | FormatToken::Fill(_) => String::new(),
+        _ => return Ok(()),
+    };
+    cursor.skip_spaces();
+    if !cursor.eat_ignore_case(text.trim()) {
+        return Err(cursor.mismatch());
+    }
+    cursor.skip_spaces();
+    Ok(())
+}
+
+/// Fields read from a date, time or duration section
+#[derive(Debug, Default)]
+struct DateFields {
+    year: Option<i32>,
+    month: Option<u32>,
+    day: Option<u32>,
+    hour: Option<u64>,
+    minute: Option<u64>,
+    second: Option<u64>,
+    nanos: u32,
+    pm: Option<bool>,
+    /// Unit of the elapsed-time token, which is not limited to its usual range
+    elapsed: Option<ElapsedUnit>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum ElapsedUnit {
+    Hours,
+    Minutes,
+    Seconds,
+}
+
+/// Match a date, time or duration section and return its serial number
+fn parse_datetime(
+    cursor: &mut Cursor,
+    section: &FormatSection,
+    locale: &LocaleSettings,
+    options: &FormatOptions,
+) -> Result<f64, InputError> {
+    let mut fields = DateFields::default();
+    let tokens = &section.tokens;
+    let is_duration = datetime::section_is_duration(section);
+    let mut skip_placeholders = false;
+
+    for (index, token) in tokens.iter().enumerate() {
+        if skip_placeholders && *token == FormatToken::DigitOrZero {
+            continue;
+        }
+        skip_placeholders = false;
+        match token {
+            FormatToken::YearFourDigit => {
+                let digits = cursor.digits(4).ok_or_else(|| cursor.mismatch())?;
+                fields.year = Some(parse_int(digits).ok_or(InputError::InvalidDate)? as i32);
+            }
+            FormatToken::YearTwoDigit => {
+                // Excel reads 00-29 as 2000-2029 and 30-99 as 1930-1999
+                let digits = cursor.digits(2).ok_or_else(|| cursor.mismatch())?;
+                let year = parse_int(digits).ok_or(InputError::InvalidDate)? as i32;
+                fields.year = Some(if year < 30 { 2000 + year } else { 1900 + year });
+            }
+            // As in the formatter, an undecided `m` is minutes in a duration and months elsewhere
+            FormatToken::MonthOrMinute1 | FormatToken::MonthOrMinute2 if is_duration => {
+                fields.minute = Some(
+                    parse_int(cursor.digits(2).ok_or_else(|| cursor.mismatch())?)
+                        .ok_or(InputError::InvalidDate)?,
+                );
+            }
+            FormatToken::MonthNum
+            | FormatToken::MonthNumPadded
+            | FormatToken::MonthOrMinute1
+            | FormatToken::MonthOrMinute2 => {
+                let month = cursor.digits(2).ok_or_else(|| cursor.mismatch())?;
+                fields.month = Some(parse_int(month).ok_or(InputError::InvalidDate)? as u32);
+            }
+            FormatToken::MonthAbbr | FormatToken::MonthFullName => {
+                let names = locale.month_names.iter().chain(&locale.short_month_names);
+                let index = cursor.eat_name(names).ok_or_else(|| cursor.mismatch())?;
+                fields.month = Some((index % 12) as u32 + 1);
+            }
+            FormatToken::DayNum | FormatToken::DayNumPadded => {
+                let day = cursor.digits(2).ok_or_else(|| cursor.mismatch())?;
+                fields.day = Some(parse_int(day).ok_or(InputError::InvalidDate)? as u32);
+            }
+            FormatToken::WeekdayAbbr | FormatToken::WeekdayFullName => {
+                // The weekday follows from the date, so it is only checked for shape
+                let names = locale.day_names.iter().chain(&locale.short_day_names);
+                cursor.eat_name(names).ok_or_else(|| cursor.mismatch())?;
+            }
+            FormatToken::Hour12Or24 | FormatToken::Hour12Or24Padded => {
+                fields.hour = Some(
+                    parse_int(cursor.digits(2).ok_or_else(|| cursor.mismatch())?)
+                        .ok_or(InputError::InvalidDate)?,
+                );
+            }
+            FormatToken::MinuteNum | FormatToken::MinuteNumPadded => {
+                fields.minute = Some(
+                    parse_int(cursor.digits(2).ok_or_else(|| cursor.mismatch())?)
+                        .ok_or(InputError::InvalidDate)?,
+                );
+            }
+            FormatToken::SecondNum | FormatToken::SecondNumPadded => {
+                fields.second = Some(
+                    parse_int(cursor.digits(2).ok_or_else(|| cursor.mismatch())?)
+                        .ok_or(InputError::InvalidDate)?,
+                );
+            }
+            FormatToken::ElapsedHours | FormatToken::ElapsedHoursPadded => {
+                let hours = cursor.digits(usize::MAX).ok_or_else(|| cursor.mismatch())?;
+                fields.hour = Some(parse_int(hours).ok_or(InputError::InvalidDate)?);
+                fields.elapsed = Some(ElapsedUnit::Hours);
+            }
+            FormatToken::ElapsedMinutes | FormatToken::ElapsedMinutesPadded => {
+                let minutes = cursor.digits(usize::MAX).ok_or_else(|| cursor.mismatch())?;
+                fields.minute = Some(parse_int(minutes).ok_or(InputError::InvalidDate)?);
+                fields.elapsed = Some(ElapsedUnit::Minutes);
+            }
+            FormatToken::ElapsedSeconds | FormatToken::ElapsedSecondsPadded => {
+                let seconds = cursor.digits(usize::MAX).ok_or_else(|| cursor.mismatch())?;
+                fields.second = Some(parse_int(seconds).ok_or(InputError::InvalidDate)?);
+                fields.elapsed = Some(ElapsedUnit::Seconds);
+            }
+            FormatToken::AmPm(_) => {
+                let index = cursor
+                    .eat_name(locale.ampm_markers.iter())
+                    .ok_or_else(|| cursor.mismatch())?;
+                fields.pm = Some(index == 1);
+            }
+            FormatToken::AP(_) => {
+                if cursor.eat_ignore_case("A") {
+                    fields.pm = Some(false);
+                } else if cursor.eat_ignore_case("P") {
+                    fields.pm = Some(true);
+                } else {
+                    return Err(cursor.mismatch());
+                }
+            }
+            FormatToken::DecimalPoint => {
+                let precision = tokens[index + 1..]
+                    .iter()
+                    .take_while(|t| **t == FormatToken::DigitOrZero)
+                    .count();
+                // A point that is not followed by `0` placeholders is a plain separator
+                if !(cursor.eat_char('.') || cursor.eat_char(locale.decimal_point)) {
+                    return Err(cursor.mismatch());
+                }
+                if precision > 0 {
+                    let digits = cursor.digits(9).ok_or_else(|| cursor.mismatch())?;
+                    fields.nanos = format!("{:0<9}", digits).parse().unwrap_or(0);
+                    skip_placeholders = true;
+                }
+            }
+            // Number placeholders are not displayed in date sections
+            FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace => {}
+            _ => match_literal(cursor, token, locale)?,
+        }
+    }
+
+    if fields.elapsed.is_some() {
+        duration_serial(&fields)
+    } else {
+        point_in_time_serial(&fields, options)
+    }
+}
+
+/// Serial number of a duration, in days
+fn duration_serial(fields: &DateFields) -> Result<f64, InputError> {
+    let hours = fields.hour.unwrap_or(0);
+    let minutes = fields.minute.unwrap_or(0);
+    let seconds = fields.second.unwrap_or(0);
+    // Only the largest unit may exceed its usual range
+    let limited = match fields.elapsed {
+        Some(ElapsedUnit::Hours) => minutes < 60 && seconds < 60,
+        Some(ElapsedUnit::Minutes) => seconds < 60,
+        _ => true,
+    };
+    if !limited || fields.pm.is_some() {
+        return Err(InputError::InvalidDate);
+    }
+    let total_seconds = hours
+        .checked_mul(3600)
+        .and_then(|total| total.checked_add(minutes.checked_mul(60)?))
+        .and_then(|total| total.checked_add(seconds))
+        .ok_or(InputError::InvalidDate)? as f64;
+    Ok((total_seconds + f64::from(fields.nanos) / 1e9) / 86400.0)
+}
+
+/// Serial number of a date and time, or the fraction of a day of a bare time
+fn point_in_time_serial(fields: &DateFields, options: &FormatOptions) -> Result<f64, InputError> {
+    let mut hour = fields.hour.unwrap_or(0);
+    if let Some(pm) = fields.pm {
+        if !(1..=12).contains(&hour) {
+            return Err(InputError::InvalidDate);
+        }
+        hour = hour % 12 + if pm { 12 } else { 0 };
+    }
+    let time = NaiveTime::from_hms_nano_opt(
+        hour.try_into().map_err(|_| InputError::InvalidDate)?,
+        fields
+            .minute
+            .unwrap_or(0)
+            .try_into()
+            .map_err(|_| InputError::InvalidDate)?,
+        fields
+            .second
+            .unwrap_or(0)
+            .try_into()
+            .map_err(|_| InputError::InvalidDate)?,
+        fields.nanos,
+    )
+    .ok_or(InputError::InvalidDate)?;
+
+    if fields.year.is_none() && fields.month.is_none() && fields.day.is_none() {
+        return Ok(datetime::time_fraction(&time));
+    }
+    // A date without a year cannot be placed; a missing month or day is the first one
+    let year = fields.year.ok_or(InputError::InvalidDate)?;
+    let date = NaiveDate::from_ymd_opt(year, fields.month.unwrap_or(1), fields.day.unwrap_or(1))
+        .ok_or(InputError::InvalidDate)?;
+    let dt = date.and_time(time);
+    if options.proleptic_dates {
+        datetime::convert_datetime_to_f64_proleptic(&dt, options.date_system)
+    } else {
+        datetime::convert_datetime_to_f64(&dt, options.date_system)
+    }
+    .ok_or(InputError::InvalidDate)
+}
+
+/// The value of a run of digits, or `None` when it does not fit a `u64`
+fn parse_int(digits: &str) -> Option<u64> {
+    digits.parse().ok()
+}
+
+/// Position in the input, ignoring leading and trailing whitespace
+struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+    end: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn new(input: &'a str) -> Self {
+        let end = input.trim_end().len();
+        let pos = input.len() - input.trim_start().len();
+        Cursor {
+            input,
+            pos: pos.min(end),
+            end,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.input[self.pos..self.end]
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.pos >= self.end
+    }
+
+    fn mismatch(&self) -> InputError {
+        InputError::Mismatch { position: self.pos }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn eat_char(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_spaces(&mut self) {
+        while let Some(c) = self.peek()
+            && c.is_whitespace()
+        {
+            self.pos += c.len_utf8();
+        }
+    }
+
+    /// Consume `text` if the input starts with it, ignoring case
+    fn eat_ignore_case(&mut self, text: &str) -> bool {
+        match prefix_len_ignore_case(self.rest(), text) {
+            Some(len) => {
+                self.pos += len;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Consume the longest of the names the input starts with and return its index
+    fn eat_name<'n>(&mut self, names: impl Iterator<Item = &'n String>) -> Option<usize> {
+        let rest = self.rest();
+        let (index, len) = names
+            .enumerate()
+            .filter(|(_, name)| !name.is_empty())
+            .filter_map(|(index, name)| Some((index, prefix_len_ignore_case(rest, name)?)))
+            .max_by_key(|&(index, len)| (len, usize::MAX - index))?;
+        self.pos += len;
+        Some(index)
+    }
+
+    /// Consume one to `max` ASCII digits
+    fn digits(&mut self, max: usize) -> Option<&'a str> {
+        let rest = self.rest();
+        let len = rest
+            .bytes()
+            .take(max)
+            .take_while(u8::is_ascii_digit)
+            .count();
+        if len == 0 {
+            return None;
+        }
+        self.pos += len;
+        Some(&rest[..len])
+    }
+
+    /// Consume the digits shown by a run of placeholders
+    ///
+    /// A grouping separator is only taken between two digits, and spaces only
+    /// where `?` placeholders may have put them.
+    fn digit_run(&mut self, grouping: Option<char>, allow_spaces: bool) -> String {
+        let mut digits = String::new();
+        while let Some(c) = self.peek() {
+            let next_is_digit =
+                self.rest()[c.len_utf8()..].starts_with(|next: char| next.is_ascii_digit());
+            let is_separator = Some(c) == grouping && !digits.is_empty() && next_is_digit;
+            if !(c.is_ascii_digit() || is_separator || allow_spaces && c == ' ') {
+                break;
+            }
+            if c.is_ascii_digit() {
+                digits.push(c);
+            }
+            self.pos += c.len_utf8();
+        }
+        digits
+    }
+
+    /// Consume an optionally signed integer, such as an exponent
+    fn signed_integer(&mut self) -> Option<i32> {
+        let negative = self.eat_char('-');
+        if !negative {
+            self.eat_char('+');
+        }
+        let value = parse_int(self.digits(usize::MAX)?)
+            .and_then(|value| i32::try_from(value).ok())
+            .unwrap_or(i32::MAX);
+        Some(if negative { -value } else { value })
+    }
+
+    /// Consume a number as `General` shows it: digits, decimals and an exponent
+    fn general_number(&mut self, locale: &LocaleSettings) -> Option<(String, String, i32)> {
+        let whole = self.digits(usize::MAX).unwrap_or("").to_string();
+        let decimals = if self.eat_char(locale.decimal_point) {
+            self.digits(usize::MAX).unwrap_or("").to_string()
+        } else {
+            String::new()
+        };
+        if whole.is_empty() && decimals.is_empty() {
+            return None;
+        }
+        let exponent = if self.eat_char('E') || self.eat_char('e') {
+            self.signed_integer()?
+        } else {
+            0
+        };
+        Some((whole, decimals, exponent))
+    }
+}
+
+/// Byte length of `prefix` at the start of `text` when case is ignored
+fn prefix_len_ignore_case(text: &str, prefix: &str) -> Option<usize> {
+    let mut text_chars = text.char_indices();
+    for expected in prefix.chars() {
+        let (_, actual) = text_chars.next()?;
+        if !actual.to_lowercase().eq(expected.to_lowercase()) {
+            return None;
+        }
+    }
+    Some(text_chars.next().map_or(text.len(), |(index, _)| index))
+}
@@ -1,6 +1,9 @@
 pub mod parser;
-pub use parser::parse_number_format;
+pub use parser::{ParseError, parse_number_format, parse_number_format_with_options};
 pub mod formatter;
+pub mod input;
+pub use input::{InputError, parse_value, parse_value_with_options};
+pub mod lint;
 pub mod locale;
 pub mod types;
 
@@ -31,3 +34,246 @@ use types::LocaleSettings;
 pub fn format_number(value: f64, format: &types::NumberFormat, locale: &LocaleSettings) -> String {
     formatter::format_number(value, format, locale)
 }
+
+/// Formats a number with options such as the date system.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_number_with_options};
+/// use number_format::types::{DateSystem, FormatOptions, LocaleSettings};
+///
+/// let fmt = parse_number_format("d mmm yyyy").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// let formatted = format_number_with_options(43830.0, &fmt, &LocaleSettings::default(), &options);
+/// assert_eq!(formatted, "1 Jan 2024");
+/// ```
+pub fn format_number_with_options(
+    value: f64,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    options: &types::FormatOptions,
+) -> String {
+    formatter::format_number_with_options(value, format, locale, options)
+}
+
+/// Formats a number and reports the color, section and interpretation used.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_number_rich};
+/// use number_format::types::{ColorType, LocaleSettings};
+///
+/// let fmt = parse_number_format("[Blue]0.00;[Red]-0.00").unwrap();
+/// let result = format_number_rich(-1.5, &fmt, &LocaleSettings::default());
+/// assert_eq!(result.text, "-1.50");
+/// assert_eq!(result.color, Some(ColorType::Red));
+/// ```
+pub fn format_number_rich(
+    value: f64,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+) -> types::FormattedValue {
+    formatter::format_number_rich(value, format, locale)
+}
+
+/// Formats a number for a fixed-width column, expanding `*` fill characters.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_number_with_width};
+/// use number_format::types::LocaleSettings;
+///
+/// let fmt = parse_number_format("$* #,##0").unwrap();
+/// let formatted = format_number_with_width(1234.0, &fmt, &LocaleSettings::default(), 10);
+/// assert_eq!(formatted, "$    1,234");
+/// ```
+pub fn format_number_with_width(
+    value: f64,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+) -> String {
+    formatter::format_number_with_width(value, format, locale, width)
+}
+
+/// Formats a number for a fixed-width column with options such as the date system.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_number_with_width_and_options};
+/// use number_format::types::{DateSystem, FormatOptions, LocaleSettings};
+///
+/// let fmt = parse_number_format("d mmm yyyy* ").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// let formatted = format_number_with_width_and_options(
+///     43830.0,
+///     &fmt,
+///     &LocaleSettings::default(),
+///     12,
+///     &options,
+/// );
+/// assert_eq!(formatted, "1 Jan 2024  ");
+/// ```
+pub fn format_number_with_width_and_options(
+    value: f64,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+    options: &types::FormatOptions,
+) -> String {
+    formatter::format_number_with_width_and_options(value, format, locale, width, options)
+}
+
+/// Formats a text value for a fixed-width column, expanding `*` fill characters.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_text_with_width};
+/// use number_format::types::LocaleSettings;
+///
+/// let fmt = parse_number_format("0;-0;0;@*.").unwrap();
+/// let formatted = format_text_with_width("abc", &fmt, &LocaleSettings::default(), 6);
+/// assert_eq!(formatted, "abc...");
+/// ```
+pub fn format_text_with_width(
+    text: &str,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    width: usize,
+) -> String {
+    formatter::format_text_with_width(text, format, locale, width)
+}
+
+/// Formats a text value the way Excel displays text cells.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_text};
+/// use number_format::types::LocaleSettings;
+///
+/// let fmt = parse_number_format("0.00;-0.00;0;\"<\"@\">\"").unwrap();
+/// assert_eq!(format_text("abc", &fmt, &LocaleSettings::default()), "<abc>");
+/// ```
+pub fn format_text(text: &str, format: &types::NumberFormat, locale: &LocaleSettings) -> String {
+    formatter::format_text(text, format, locale)
+}
+
+/// Formats a text value and reports the color and section used.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_text_rich};
+/// use number_format::types::{ColorType, LocaleSettings};
+///
+/// let fmt = parse_number_format("0;-0;0;[Red]@").unwrap();
+/// let result = format_text_rich("abc", &fmt, &LocaleSettings::default());
+/// assert_eq!(result.text, "abc");
+/// assert_eq!(result.color, Some(ColorType::Red));
+/// ```
+pub fn format_text_rich(
+    text: &str,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+) -> types::FormattedValue {
+    formatter::format_text_rich(text, format, locale)
+}
+
+/// Formats a spreadsheet cell value: number, text, boolean, error or empty.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_value};
+/// use number_format::types::{CellValue, LocaleSettings};
+///
+/// let fmt = parse_number_format("#,##0").unwrap();
+/// let german = number_format::get_locale_settings("de").unwrap();
+/// assert_eq!(format_value(&CellValue::Bool(false), &fmt, &german), "FALSCH");
+/// assert_eq!(format_value(&CellValue::Empty, &fmt, &LocaleSettings::default()), "");
+/// ```
+pub fn format_value(
+    value: &types::CellValue,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+) -> String {
+    formatter::format_value(value, format, locale)
+}
+
+/// Formats a spreadsheet cell value with options such as the date system.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_value_with_options};
+/// use number_format::types::{CellValue, DateSystem, FormatOptions, LocaleSettings};
+///
+/// let fmt = parse_number_format("d mmm yyyy").unwrap();
+/// let options = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+/// let value = CellValue::Number(43830.0);
+/// let formatted = format_value_with_options(&value, &fmt, &LocaleSettings::default(), &options);
+/// assert_eq!(formatted, "1 Jan 2024");
+/// ```
+pub fn format_value_with_options(
+    value: &types::CellValue,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    options: &types::FormatOptions,
+) -> String {
+    formatter::format_value_with_options(value, format, locale, options)
+}
+
+/// Formats a chrono date, time or duration without rounding it through a serial number.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_temporal};
+/// use number_format::types::{FormatOptions, LocaleSettings, TemporalValue};
+///
+/// let fmt = parse_number_format("[h]:mm:ss").unwrap();
+/// let duration = TemporalValue::from(chrono::Duration::seconds(100_000));
+/// let formatted = format_temporal(&duration, &fmt, &LocaleSettings::default(), &FormatOptions::default());
+/// assert_eq!(formatted, "27:46:40");
+/// ```
+pub fn format_temporal(
+    value: &types::TemporalValue,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+    options: &types::FormatOptions,
+) -> String {
+    formatter::format_temporal(value, format, locale, options)
+}
+
+/// Formats an exact decimal number, such as an amount stored as text, without `f64` rounding.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_decimal};
+/// use number_format::types::{Decimal, LocaleSettings};
+///
+/// let fmt = parse_number_format("0.00").unwrap();
+/// let value: Decimal = "1.005".parse().unwrap();
+/// assert_eq!(format_decimal(&value, &fmt, &LocaleSettings::default()), "1.01");
+/// ```
+pub fn format_decimal(
+    value: &types::Decimal,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+) -> String {
+    formatter::format_decimal(value, format, locale)
+}
+
+/// Formats an `i64`, `u64`, `i128` or `u128` without converting it to `f64`.
+///
+/// # Examples
+/// ```
+/// use number_format::{parse_number_format, format_integer};
+/// use number_format::types::LocaleSettings;
+///
+/// let fmt = parse_number_format("000-00-0000").unwrap();
+/// assert_eq!(format_integer(123456789_i64, &fmt, &LocaleSettings::default()), "123-45-6789");
+/// ```
+pub fn format_integer<T: Into<types::Decimal>>(
+    value: T,
+    format: &types::NumberFormat,
+    locale: &LocaleSettings,
+) -> String {
+    formatter::format_integer(value, format, locale)
+}
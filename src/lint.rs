@@ -0,0 +1,295 @@
+//! Warnings for format codes that parse but probably do not do what was intended
+//!
+//! Format codes copied from the web often contain constructs that Excel accepts
+//! silently: placeholders that a date section ignores, conditions that shadow
+//! each other, or `m` read as month where minutes were meant. `lint` reports
+//! them without rejecting the format.
+
+use std::fmt;
+
+use crate::formatter::sections::select_section_index;
+use crate::types::{FormatSection, FormatToken, NumberFormat};
+
+/// A suspicious or dead construct in a parsed format
+///
+/// `section` is the index of the section (0 = positive, 1 = negative, 2 = zero,
+/// 3 = text) and `token` the index into that section's `tokens`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LintWarning {
+    /// `m`/`mm` was read as month although it stands next to an hour or second token
+    AmbiguousMonth { section: usize, token: usize },
+    /// No value can ever select this section given the conditions before it
+    UnreachableSection { section: usize },
+    /// The third section follows two conditional sections, so it is not a zero section
+    ZeroSectionAfterConditions { section: usize },
+    /// A number placeholder inside a date or time section, which is not displayed
+    IgnoredInDateSection { section: usize, token: usize },
+    /// `E+`/`E-` without digit placeholders on both sides, or repeated in one section
+    StrayExponent { section: usize, token: usize },
+    /// `_(` without a closing parenthesis, or `_)` that lines up with no parenthesis
+    UnbalancedPadding { section: usize },
+}
+
+impl LintWarning {
+    /// Index of the section the warning applies to
+    pub fn section(&self) -> usize {
+        match self {
+            LintWarning::AmbiguousMonth { section, .. }
+            | LintWarning::UnreachableSection { section }
+            | LintWarning::ZeroSectionAfterConditions { section }
+            | LintWarning::IgnoredInDateSection { section, .. }
+            | LintWarning::StrayExponent { section, .. }
+            | LintWarning::UnbalancedPadding { section } => *section,
+        }
+    }
+
+    /// Index of the offending token within the section, if the warning is about a single token
+    pub fn token(&self) -> Option<usize> {
+        match self {
+            LintWarning::AmbiguousMonth { token, .. }
+            | LintWarning::IgnoredInDateSection { token, .. }
+            | LintWarning::StrayExponent { token, .. } => Some(*token),
+            LintWarning::UnreachableSection { .. }
+            | LintWarning::ZeroSectionAfterConditions { .. }
+            | LintWarning::UnbalancedPadding { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LintWarning::AmbiguousMonth { .. } => {
+                write!(f, "'m' next to an hour or second is read as month")?
+            }
+            LintWarning::UnreachableSection { .. } => {
+                write!(f, "Section can never be used because of the conditions")?
+            }
+            LintWarning::ZeroSectionAfterConditions { .. } => write!(
+                f,
+                "Section after two conditional sections is used for all other values, not only zero"
+            )?,
+            LintWarning::IgnoredInDateSection { .. } => {
+                write!(f, "Number placeholder is ignored in a date or time section")?
+            }
+            LintWarning::StrayExponent { .. } => {
+                write!(f, "Exponent without digit placeholders on both sides")?
+            }
+            LintWarning::UnbalancedPadding { .. } => {
+                write!(f, "'_(' or '_)' padding has no matching parenthesis")?
+            }
+        }
+        write!(f, " (section {})", self.section() + 1)
+    }
+}
+
+/// Report suspicious or dead constructs in a parsed format
+///
+/// The format is not modified; an empty result means nothing looked wrong.
+///
+/// # Examples
+/// ```
+/// use number_format::lint::{LintWarning, lint};
+/// use number_format::parse_number_format;
+///
+/// let format = parse_number_format("[>=0]0;[>10]0.0").unwrap();
+/// assert_eq!(lint(&format), vec![LintWarning::UnreachableSection { section: 1 }]);
+/// ```
+pub fn lint(format: &NumberFormat) -> Vec<LintWarning> {
+    let sections: Vec<(usize, &FormatSection)> = [
+        Some(&format.positive_section),
+        format.negative_section.as_ref(),
+        format.zero_section.as_ref(),
+        format.text_section.as_ref(),
+    ]
+    .into_iter()
+    .enumerate()
+    .filter_map(|(i, s)| s.map(|s| (i, s)))
+    .collect();
+
+    let mut warnings = Vec::new();
+    lint_section_selection(format, &mut warnings);
+    for &(index, section) in &sections {
+        lint_month_minute(index, section, &mut warnings);
+        if section
+            .tokens
+            .iter()
+            .any(FormatToken::is_datetime_placeholder)
+        {
+            lint_date_placeholders(index, section, &mut warnings);
+        } else {
+            lint_exponents(index, section, &mut warnings);
+        }
+    }
+    lint_padding(&sections, &mut warnings);
+    warnings
+}
+
+/// Find sections that no value selects, and a third section that only looks like a zero section
+fn lint_section_selection(format: &NumberFormat, warnings: &mut Vec<LintWarning>) {
+    let conditional = [
+        Some(&format.positive_section),
+        format.negative_section.as_ref(),
+        format.zero_section.as_ref(),
+    ]
+    .map(|s| s.and_then(|s| s.condition.as_ref()));
+    if conditional.iter().all(Option::is_none) {
+        return;
+    }
+
+    // Section selection only changes at zero and at the condition values, so
+    // probing those values, the midpoints between them and one point beyond
+    // each end covers every case.
+    let mut bounds: Vec<f64> = conditional.iter().flatten().map(|c| c.value).collect();
+    bounds.push(0.0);
+    bounds.sort_by(f64::total_cmp);
+    bounds.dedup();
+    let mut probes = vec![bounds[0] - 1.0, bounds[bounds.len() - 1] + 1.0];
+    for pair in bounds.windows(2) {
+        probes.push(pair[0]);
+        probes.push((pair[0] + pair[1]) / 2.0);
+    }
+    probes.push(bounds[bounds.len() - 1]);
+
+    let mut reached = [false; 3];
+    for &probe in &probes {
+        reached[select_section_index(probe, format)] = true;
+    }
+
+    let present = [
+        true,
+        format.negative_section.is_some(),
+        format.zero_section.is_some(),
+    ];
+    for section in 0..3 {
+        if present[section] && !reached[section] {
+            warnings.push(LintWarning::UnreachableSection { section });
+        }
+    }
+
+    if conditional[0].is_some()
+        && conditional[1].is_some()
+        && conditional[2].is_none()
+        && present[2]
+        && reached[2]
+    {
+        warnings.push(LintWarning::ZeroSectionAfterConditions { section: 2 });
+    }
+}
+
+/// Find `m`/`mm` resolved as month whose nearest date/time neighbour is an hour or second
+fn lint_month_minute(index: usize, section: &FormatSection, warnings: &mut Vec<LintWarning>) {
+    let tokens = &section.tokens;
+    let is_time_neighbour = |token: Option<&FormatToken>| {
+        matches!(
+            token,
+            Some(
+                FormatToken::Hour12Or24
+                    | FormatToken::Hour12Or24Padded
+                    | FormatToken::SecondNum
+                    | FormatToken::SecondNumPadded
+            )
+        )
+    };
+
+    for (i, token) in tokens.iter().enumerate() {
+        if !matches!(token, FormatToken::MonthNum | FormatToken::MonthNumPadded) {
+            continue;
+        }
+        let before = tokens[..i]
+            .iter()
+            .rev()
+            .find(|t| t.is_datetime_placeholder());
+        let after = tokens[i + 1..].iter().find(|t| t.is_datetime_placeholder());
+        if is_time_neighbour(before) || is_time_neighbour(after) {
+            warnings.push(LintWarning::AmbiguousMonth {
+                section: index,
+                token: i,
+            });
+        }
+    }
+}
+
+/// Find number placeholders that a date or time section does not display
+fn lint_date_placeholders(index: usize, section: &FormatSection, warnings: &mut Vec<LintWarning>) {
+    // `0` is only used for fractional seconds, directly after a decimal point
+    let mut in_fraction = false;
+    for (i, token) in section.tokens.iter().enumerate() {
+        let ignored = match token {
+            FormatToken::DecimalPoint => {
+                in_fraction = true;
+                continue;
+            }
+            FormatToken::DigitOrZero => !in_fraction,
+            FormatToken::DigitIfNeeded
+            | FormatToken::DigitOrSpace
+            | FormatToken::Percentage
+            | FormatToken::Exponential(_)
+            | FormatToken::TextValue => true,
+            _ => false,
+        };
+        if !matches!(token, FormatToken::DigitOrZero) {
+            in_fraction = false;
+        }
+        if ignored {
+            warnings.push(LintWarning::IgnoredInDateSection {
+                section: index,
+                token: i,
+            });
+        }
+    }
+}
+
+/// Find exponents without a mantissa or exponent digits, and repeated exponents
+fn lint_exponents(index: usize, section: &FormatSection, warnings: &mut Vec<LintWarning>) {
+    let is_digit = |t: &FormatToken| {
+        matches!(
+            t,
+            FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+        )
+    };
+
+    let mut seen_exponent = false;
+    for (i, token) in section.tokens.iter().enumerate() {
+        if !matches!(token, FormatToken::Exponential(_)) {
+            continue;
+        }
+        let has_mantissa = section.tokens[..i].iter().any(is_digit);
+        let has_exponent_digits = section.tokens[i + 1..].iter().any(is_digit);
+        if seen_exponent || !has_mantissa || !has_exponent_digits {
+            warnings.push(LintWarning::StrayExponent {
+                section: index,
+                token: i,
+            });
+        }
+        seen_exponent = true;
+    }
+}
+
+/// Find `_(` without a closing parenthesis and `_)` that mirrors no parenthesis anywhere
+fn lint_padding(sections: &[(usize, &FormatSection)], warnings: &mut Vec<LintWarning>) {
+    let has_padding =
+        |section: &FormatSection, c: char| section.tokens.contains(&FormatToken::SkipWidth(c));
+    let has_literal = |section: &FormatSection, c: char| {
+        section.tokens.iter().any(|t| match t {
+            FormatToken::LiteralChar(ch) => *ch == c,
+            FormatToken::QuotedText(text) => text.contains(c),
+            _ => false,
+        })
+    };
+
+    for &(index, section) in sections {
+        let unclosed =
+            has_padding(section, '(') && !has_padding(section, ')') && !has_literal(section, ')');
+        // A lone `_)` usually lines a section up with the `)` of a negative section
+        let unopened = has_padding(section, ')')
+            && !has_padding(section, '(')
+            && !has_literal(section, '(')
+            && !sections
+                .iter()
+                .any(|&(other, s)| other != index && has_literal(s, ')'));
+        if unclosed || unopened {
+            warnings.push(LintWarning::UnbalancedPadding { section: index });
+        }
+    }
+}
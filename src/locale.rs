@@ -150,83 +150,120 @@ impl LocaleManager {
         let mut settings = LocaleSettings::default();
 
         // Decimal point
-        if let Some(decimal) = table.get("decimal").and_then(|v| v.as_str()) {
-            if let Some(c) = decimal.chars().next() {
-                settings.decimal_point = c;
-            }
+        if let Some(decimal) = table.get("decimal").and_then(|v| v.as_str())
+            && let Some(c) = decimal.chars().next()
+        {
+            settings.decimal_point = c;
         }
 
         // Thousands separator
-        if let Some(group) = table.get("group").and_then(|v| v.as_str()) {
-            if let Some(c) = group.chars().next() {
-                settings.thousands_separator = c;
-            }
+        if let Some(group) = table.get("group").and_then(|v| v.as_str())
+            && let Some(c) = group.chars().next()
+        {
+            settings.thousands_separator = c;
         }
 
         // AM/PM markers
-        if let Some(ampm) = table.get("ampm").and_then(|v| v.as_array()) {
-            if ampm.len() >= 2 {
-                let am = ampm[0].as_str().unwrap_or("AM").to_string();
-                let pm = ampm[1].as_str().unwrap_or("PM").to_string();
-                settings.ampm_markers = [am, pm];
-            }
+        if let Some(ampm) = table.get("ampm").and_then(|v| v.as_array())
+            && ampm.len() >= 2
+        {
+            let am = ampm[0].as_str().unwrap_or("AM").to_string();
+            let pm = ampm[1].as_str().unwrap_or("PM").to_string();
+            settings.ampm_markers = [am, pm];
+        }
+
+        // Boolean names
+        if let Some(names) = table.get("bool").and_then(|v| v.as_array())
+            && names.len() >= 2
+        {
+            let true_name = names[0].as_str().unwrap_or("TRUE").to_string();
+            let false_name = names[1].as_str().unwrap_or("FALSE").to_string();
+            settings.boolean_names = [true_name, false_name];
         }
 
         // Month names (full)
-        if let Some(months) = table.get("month_names").and_then(|v| v.as_array()) {
-            if months.len() == 12 {
-                let month_names: Vec<String> = months
-                    .iter()
-                    .map(|m| m.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = month_names.clone().try_into() {
-                    settings.month_names = array;
-                }
-                settings.month_names_full = month_names;
+        if let Some(months) = table.get("month_names").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_names: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_names.clone().try_into() {
+                settings.month_names = array;
             }
+            settings.month_names_full = month_names;
         }
 
         // Month abbreviations
-        if let Some(months) = table.get("month_abbreviations").and_then(|v| v.as_array()) {
-            if months.len() == 12 {
-                let month_abbrs: Vec<String> = months
-                    .iter()
-                    .map(|m| m.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = month_abbrs.clone().try_into() {
-                    settings.short_month_names = array;
-                }
-                settings.month_names_abbr = month_abbrs;
+        if let Some(months) = table.get("month_abbreviations").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_abbrs: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_abbrs.clone().try_into() {
+                settings.short_month_names = array;
+            }
+            settings.month_names_abbr = month_abbrs;
+        }
+
+        // Hijri month names
+        if let Some(months) = table.get("hijri_month_names").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_names: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_names.try_into() {
+                settings.hijri_month_names = array;
+            }
+        }
+
+        // Quarter names
+        if let Some(quarters) = table.get("quarter_names").and_then(|v| v.as_array())
+            && quarters.len() == 4
+        {
+            let quarter_names: Vec<String> = quarters
+                .iter()
+                .map(|q| q.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = quarter_names.try_into() {
+                settings.quarter_names = array;
             }
         }
 
         // Day names (full)
-        if let Some(days) = table.get("day_names").and_then(|v| v.as_array()) {
-            if days.len() == 7 {
-                let day_names: Vec<String> = days
-                    .iter()
-                    .map(|d| d.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = day_names.try_into() {
-                    settings.day_names = array;
-                }
+        if let Some(days) = table.get("day_names").and_then(|v| v.as_array())
+            && days.len() == 7
+        {
+            let day_names: Vec<String> = days
+                .iter()
+                .map(|d| d.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = day_names.try_into() {
+                settings.day_names = array;
             }
         }
 
         // Day abbreviations
-        if let Some(days) = table.get("day_abbreviations").and_then(|v| v.as_array()) {
-            if days.len() == 7 {
-                let day_abbrs: Vec<String> = days
-                    .iter()
-                    .map(|d| d.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = day_abbrs.try_into() {
-                    settings.short_day_names = array;
-                }
+        if let Some(days) = table.get("day_abbreviations").and_then(|v| v.as_array())
+            && days.len() == 7
+        {
+            let day_abbrs: Vec<String> = days
+                .iter()
+                .map(|d| d.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = day_abbrs.try_into() {
+                settings.short_day_names = array;
             }
         }
 
@@ -244,17 +281,17 @@ impl LocaleManager {
             .ok_or_else(|| LocaleError::ParseError("Locale setting is not a table".to_string()))?;
 
         // Decimal point
-        if let Some(decimal) = table.get("decimal").and_then(|v| v.as_str()) {
-            if let Some(c) = decimal.chars().next() {
-                settings.decimal_point = c;
-            }
+        if let Some(decimal) = table.get("decimal").and_then(|v| v.as_str())
+            && let Some(c) = decimal.chars().next()
+        {
+            settings.decimal_point = c;
         }
 
         // Thousands separator
-        if let Some(group) = table.get("group").and_then(|v| v.as_str()) {
-            if let Some(c) = group.chars().next() {
-                settings.thousands_separator = c;
-            }
+        if let Some(group) = table.get("group").and_then(|v| v.as_str())
+            && let Some(c) = group.chars().next()
+        {
+            settings.thousands_separator = c;
         }
 
         // Currency symbol (if applicable)
@@ -267,69 +304,106 @@ impl LocaleManager {
         }
 
         // AM/PM markers
-        if let Some(ampm) = table.get("ampm").and_then(|v| v.as_array()) {
-            if ampm.len() >= 2 {
-                let am = ampm[0].as_str().unwrap_or("AM").to_string();
-                let pm = ampm[1].as_str().unwrap_or("PM").to_string();
-                settings.ampm_markers = [am, pm];
-            }
+        if let Some(ampm) = table.get("ampm").and_then(|v| v.as_array())
+            && ampm.len() >= 2
+        {
+            let am = ampm[0].as_str().unwrap_or("AM").to_string();
+            let pm = ampm[1].as_str().unwrap_or("PM").to_string();
+            settings.ampm_markers = [am, pm];
+        }
+
+        // Boolean names
+        if let Some(names) = table.get("bool").and_then(|v| v.as_array())
+            && names.len() >= 2
+        {
+            let true_name = names[0].as_str().unwrap_or("TRUE").to_string();
+            let false_name = names[1].as_str().unwrap_or("FALSE").to_string();
+            settings.boolean_names = [true_name, false_name];
         }
 
         // Month names (full)
-        if let Some(months) = table.get("month_names").and_then(|v| v.as_array()) {
-            if months.len() == 12 {
-                let month_names: Vec<String> = months
-                    .iter()
-                    .map(|m| m.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = month_names.clone().try_into() {
-                    settings.month_names = array;
-                }
-                settings.month_names_full = month_names;
+        if let Some(months) = table.get("month_names").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_names: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_names.clone().try_into() {
+                settings.month_names = array;
             }
+            settings.month_names_full = month_names;
         }
 
         // Month abbreviations
-        if let Some(months) = table.get("month_abbreviations").and_then(|v| v.as_array()) {
-            if months.len() == 12 {
-                let month_abbrs: Vec<String> = months
-                    .iter()
-                    .map(|m| m.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = month_abbrs.clone().try_into() {
-                    settings.short_month_names = array;
-                }
-                settings.month_names_abbr = month_abbrs;
+        if let Some(months) = table.get("month_abbreviations").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_abbrs: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_abbrs.clone().try_into() {
+                settings.short_month_names = array;
+            }
+            settings.month_names_abbr = month_abbrs;
+        }
+
+        // Hijri month names
+        if let Some(months) = table.get("hijri_month_names").and_then(|v| v.as_array())
+            && months.len() == 12
+        {
+            let month_names: Vec<String> = months
+                .iter()
+                .map(|m| m.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = month_names.try_into() {
+                settings.hijri_month_names = array;
+            }
+        }
+
+        // Quarter names
+        if let Some(quarters) = table.get("quarter_names").and_then(|v| v.as_array())
+            && quarters.len() == 4
+        {
+            let quarter_names: Vec<String> = quarters
+                .iter()
+                .map(|q| q.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = quarter_names.try_into() {
+                settings.quarter_names = array;
             }
         }
 
         // Day names (full)
-        if let Some(days) = table.get("day_names").and_then(|v| v.as_array()) {
-            if days.len() == 7 {
-                let day_names: Vec<String> = days
-                    .iter()
-                    .map(|d| d.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = day_names.try_into() {
-                    settings.day_names = array;
-                }
+        if let Some(days) = table.get("day_names").and_then(|v| v.as_array())
+            && days.len() == 7
+        {
+            let day_names: Vec<String> = days
+                .iter()
+                .map(|d| d.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = day_names.try_into() {
+                settings.day_names = array;
             }
         }
 
         // Day abbreviations
-        if let Some(days) = table.get("day_abbreviations").and_then(|v| v.as_array()) {
-            if days.len() == 7 {
-                let day_abbrs: Vec<String> = days
-                    .iter()
-                    .map(|d| d.as_str().unwrap_or("").to_string())
-                    .collect();
-
-                if let Ok(array) = day_abbrs.try_into() {
-                    settings.short_day_names = array;
-                }
+        if let Some(days) = table.get("day_abbreviations").and_then(|v| v.as_array())
+            && days.len() == 7
+        {
+            let day_abbrs: Vec<String> = days
+                .iter()
+                .map(|d| d.as_str().unwrap_or("").to_string())
+                .collect();
+
+            if let Ok(array) = day_abbrs.try_into() {
+                settings.short_day_names = array;
             }
         }
 
@@ -358,11 +432,18 @@ pub fn get_locale_settings(locale_id: &str) -> Option<LocaleSettings> {
 }
 
 /// Get locale settings by Excel-style locale code (e.g., 0x0409 for en_US)
+///
+/// Codes for regions without their own settings, like de_DE, fall back to
+/// the settings of the language.
 pub fn get_locale_settings_by_code(code: u32) -> Option<LocaleSettings> {
     let manager = LocaleManager::get();
+    let locale_id = manager.resolve_locale_code(code)?;
     manager
-        .resolve_locale_code(code)
-        .and_then(|locale_id| manager.get_locale_settings(locale_id))
+        .get_locale_settings(locale_id)
+        .or_else(|| {
+            let (language, _) = locale_id.split_once('_')?;
+            manager.get_locale_settings(language)
+        })
         .cloned()
 }
 
@@ -374,9 +455,10 @@ pub fn get_locale_settings_for_excel_code(code_str: &str) -> Option<LocaleSettin
         .strip_prefix("[$-")
         .and_then(|s| s.strip_suffix("]"))
     {
-        // Try to parse as hex
+        // Try to parse as hex; the LCID is the low 16 bits, above it are the
+        // calendar and number system bytes as in [$-1060401]
         if let Ok(code) = u32::from_str_radix(code_part, 16) {
-            return get_locale_settings_by_code(code);
+            return get_locale_settings_by_code(code & 0xFFFF);
         }
 
         // Try to parse as decimal
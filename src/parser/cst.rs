@@ -0,0 +1,186 @@
+//! Lossless concrete syntax tree for format strings
+//!
+//! `parse_number_format` normalizes its input: `YYYY` and `yyyy` become the same
+//! token, `\x` and `"x"` both become literals, and `[$US-409]` is rewritten. The
+//! concrete syntax tree keeps the original spelling and byte span of every
+//! token next to its meaning, so the exact input can be reproduced and each
+//! token can be highlighted or hovered in an editor.
+
+use std::ops::Range;
+
+use winnow::Parser;
+use winnow::combinator::opt;
+
+use crate::parser::combinators::parse_condition;
+use crate::parser::error::ParseError;
+use crate::parser::format::{classify_token_error, parse_number_format_with_options};
+use crate::parser::sections::{normalize_section_tokens, parse_denominator_digit, parse_token};
+use crate::types::{Condition, FormatToken, ParseOptions};
+
+/// What a piece of the format string means
+#[derive(Debug, Clone, PartialEq)]
+pub enum CstTokenKind {
+    /// A condition such as `[>=100]` at the start of a section
+    Condition(Condition),
+    /// A format token, with month/minute ambiguity already resolved
+    Token(FormatToken),
+    /// The `;` between two sections
+    SectionSeparator,
+}
+
+/// A piece of the format string with its original spelling and position
+#[derive(Debug, Clone, PartialEq)]
+pub struct CstToken {
+    /// What the text means
+    pub kind: CstTokenKind,
+    /// The source text exactly as written
+    pub text: String,
+    /// Byte range of `text` within the format string
+    pub span: Range<usize>,
+    /// Index of the section the token belongs to (separators belong to the section they close)
+    pub section: usize,
+}
+
+/// Concrete syntax tree of a format string
+///
+/// The tokens cover the input without gaps or overlaps, in source order.
+/// Unlike `NumberFormat`, nothing is folded away: colors stay in place,
+/// scaling commas remain `ThousandsSeparator` tokens and the digits of a fixed
+/// denominator remain literals.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatCst {
+    /// All tokens of the format string, in source order
+    pub tokens: Vec<CstToken>,
+}
+
+impl FormatCst {
+    /// Reproduce the original format string
+    pub fn to_source(&self) -> String {
+        self.tokens.iter().map(|t| t.text.as_str()).collect()
+    }
+
+    /// Tokens belonging to the given section, including its condition and closing separator
+    pub fn section_tokens(&self, section: usize) -> impl Iterator<Item = &CstToken> {
+        self.tokens.iter().filter(move |t| t.section == section)
+    }
+}
+
+/// Parse a format string into a lossless concrete syntax tree
+///
+/// Returns the same errors as `parse_number_format` for invalid input.
+///
+/// # Examples
+/// ```
+/// use number_format::parser::{CstTokenKind, parse_cst};
+/// use number_format::types::FormatToken;
+///
+/// let cst = parse_cst("YYYY\\-mm").unwrap();
+/// assert_eq!(cst.to_source(), "YYYY\\-mm");
+/// assert_eq!(cst.tokens[0].text, "YYYY");
+/// assert_eq!(cst.tokens[1].span, 4..6);
+/// assert_eq!(cst.tokens[1].kind, CstTokenKind::Token(FormatToken::LiteralChar('-')));
+/// ```
+pub fn parse_cst(input_str: &str) -> Result<FormatCst, ParseError> {
+    parse_cst_with_options(input_str, &ParseOptions::default())
+}
+
+/// Parse a format string into a concrete syntax tree with options such as the extended token set
+///
+/// Returns the same errors as `parse_number_format_with_options` for invalid input.
+///
+/// # Examples
+/// ```
+/// use number_format::parser::{CstTokenKind, parse_cst_with_options};
+/// use number_format::types::{FormatToken, ParseOptions};
+///
+/// let options = ParseOptions::default().with_extended_tokens(true);
+/// let cst = parse_cst_with_options("Q yyyy", &options).unwrap();
+/// assert_eq!(cst.tokens[0].kind, CstTokenKind::Token(FormatToken::Quarter));
+/// ```
+pub fn parse_cst_with_options(
+    input_str: &str,
+    options: &ParseOptions,
+) -> Result<FormatCst, ParseError> {
+    // Validate with the regular parser so both report the same errors
+    parse_number_format_with_options(input_str, options)?;
+
+    let mut input = input_str;
+    let offset = |remaining: &str| input_str.len() - remaining.len();
+    let mut tokens: Vec<CstToken> = Vec::new();
+    let mut section = 0;
+
+    loop {
+        if section < 3 {
+            let start = offset(input);
+            if let Ok(Some(condition)) = opt(parse_condition).parse_next(&mut input) {
+                let end = offset(input);
+                tokens.push(CstToken {
+                    kind: CstTokenKind::Condition(condition),
+                    text: input_str[start..end].to_string(),
+                    span: start..end,
+                    section,
+                });
+            }
+        }
+
+        let first_format_token = tokens.len();
+        let mut in_denominator = false;
+        while !input.is_empty() && !input.starts_with(';') {
+            let start = offset(input);
+            let previous = match tokens[first_format_token..].last() {
+                Some(CstToken {
+                    kind: CstTokenKind::Token(token),
+                    ..
+                }) => Some(token),
+                _ => None,
+            };
+            let denominator_digit = parse_denominator_digit(&mut input, previous, in_denominator);
+            in_denominator = denominator_digit.is_some();
+            let token = match denominator_digit {
+                Some(digit) => digit,
+                None => parse_token(*options)
+                    .parse_next(&mut input)
+                    .map_err(|_| classify_token_error(input_str, offset(input), section))?,
+            };
+            let end = offset(input);
+            tokens.push(CstToken {
+                kind: CstTokenKind::Token(token),
+                text: input_str[start..end].to_string(),
+                span: start..end,
+                section,
+            });
+        }
+        normalize_cst_section(&mut tokens[first_format_token..]);
+
+        if input.is_empty() {
+            break;
+        }
+        let start = offset(input);
+        input = &input[1..];
+        tokens.push(CstToken {
+            kind: CstTokenKind::SectionSeparator,
+            text: ";".to_string(),
+            span: start..start + 1,
+            section,
+        });
+        section += 1;
+    }
+
+    Ok(FormatCst { tokens })
+}
+
+/// Apply the same context-dependent rewrites as the regular parser to one section
+fn normalize_cst_section(tokens: &mut [CstToken]) {
+    let mut format_tokens: Vec<FormatToken> = tokens
+        .iter()
+        .filter_map(|t| match &t.kind {
+            CstTokenKind::Token(token) => Some(token.clone()),
+            _ => None,
+        })
+        .collect();
+    normalize_section_tokens(&mut format_tokens);
+
+    for (cst_token, token) in tokens.iter_mut().zip(format_tokens) {
+        cst_token.kind = CstTokenKind::Token(token);
+    }
+}
@@ -0,0 +1,112 @@
+//! Error type for format string parsing
+//!
+//! Every error carries the index of the section it was found in (0 = positive,
+//! 1 = negative, 2 = zero, 3 = text) and the byte range of the offending input,
+//! so callers can point at the exact characters of the format code.
+
+use std::fmt;
+use std::ops::Range;
+
+/// Error returned when a format string cannot be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseError {
+    /// A quoted literal (`"...`) is missing its closing quote
+    UnterminatedQuote { section: usize, span: Range<usize> },
+    /// A bracket (`[...`) is missing its closing `]`
+    UnterminatedBracket { section: usize, span: Range<usize> },
+    /// A bracketed tag such as `[Foo]` is not a known color, elapsed time or locale tag
+    UnknownBracketTag { section: usize, span: Range<usize> },
+    /// A bracketed condition such as `[>=abc]` could not be parsed
+    InvalidCondition { section: usize, span: Range<usize> },
+    /// A `\`, `*` or `_` at the end of the input without the character it applies to
+    DanglingModifier { section: usize, span: Range<usize> },
+    /// A character that has no meaning in a format code
+    UnexpectedCharacter {
+        section: usize,
+        span: Range<usize>,
+        found: char,
+    },
+    /// The format string has more than four sections; `section` is the text section
+    /// (3) and `span` runs from the separator after it to the end of the input
+    TooManySections { section: usize, span: Range<usize> },
+    /// More than two of the first three sections have a condition
+    TooManyConditions { section: usize, span: Range<usize> },
+    /// The text section (4th) has a condition
+    ConditionInTextSection { section: usize, span: Range<usize> },
+    /// The text section (4th) contains a numeric or date token
+    NumericTokenInTextSection { section: usize, span: Range<usize> },
+}
+
+impl ParseError {
+    /// Index of the section the error was found in
+    pub fn section(&self) -> usize {
+        match self {
+            ParseError::UnterminatedQuote { section, .. }
+            | ParseError::UnterminatedBracket { section, .. }
+            | ParseError::UnknownBracketTag { section, .. }
+            | ParseError::InvalidCondition { section, .. }
+            | ParseError::DanglingModifier { section, .. }
+            | ParseError::UnexpectedCharacter { section, .. }
+            | ParseError::TooManySections { section, .. }
+            | ParseError::TooManyConditions { section, .. }
+            | ParseError::ConditionInTextSection { section, .. }
+            | ParseError::NumericTokenInTextSection { section, .. } => *section,
+        }
+    }
+
+    /// Byte range of the offending input within the format string
+    pub fn span(&self) -> Range<usize> {
+        match self {
+            ParseError::UnterminatedQuote { span, .. }
+            | ParseError::UnterminatedBracket { span, .. }
+            | ParseError::UnknownBracketTag { span, .. }
+            | ParseError::InvalidCondition { span, .. }
+            | ParseError::DanglingModifier { span, .. }
+            | ParseError::UnexpectedCharacter { span, .. }
+            | ParseError::TooManySections { span, .. }
+            | ParseError::TooManyConditions { span, .. }
+            | ParseError::ConditionInTextSection { span, .. }
+            | ParseError::NumericTokenInTextSection { span, .. } => span.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::UnterminatedQuote { .. } => write!(f, "Unterminated quoted text")?,
+            ParseError::UnterminatedBracket { .. } => write!(f, "Unterminated bracket")?,
+            ParseError::UnknownBracketTag { .. } => write!(f, "Unknown bracket tag")?,
+            ParseError::InvalidCondition { .. } => write!(f, "Invalid condition")?,
+            ParseError::DanglingModifier { .. } => {
+                write!(f, "Missing character after '\\', '*' or '_'")?
+            }
+            ParseError::UnexpectedCharacter { found, .. } => {
+                write!(f, "Unexpected character '{}'", found)?
+            }
+            ParseError::TooManySections { .. } => {
+                write!(f, "Format string cannot have more than four sections")?
+            }
+            ParseError::TooManyConditions { .. } => write!(
+                f,
+                "Format string cannot have more than two conditional sections"
+            )?,
+            ParseError::ConditionInTextSection { .. } => {
+                write!(f, "Text section (4th) must not have a condition")?
+            }
+            ParseError::NumericTokenInTextSection { .. } => {
+                write!(f, "Text section (4th) contains a numeric or date symbol")?
+            }
+        }
+        let span = self.span();
+        write!(
+            f,
+            " at bytes {}..{} (section {})",
+            span.start,
+            span.end,
+            self.section() + 1
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
@@ -1,8 +1,16 @@
+use std::ops::Range;
+
 use winnow::Parser;
-use winnow::error::ErrMode;
+use winnow::combinator::opt;
+use winnow::error::ContextError;
 use winnow::token::literal;
 
-use crate::parser::sections::{parse_one_section, resolve_month_minute_ambiguity_in_section};
+use crate::parser::combinators::parse_condition;
+use crate::parser::error::ParseError;
+use crate::parser::sections::{
+    parse_denominator_digit, parse_one_section, parse_token,
+    resolve_month_minute_ambiguity_in_section,
+};
 use crate::types::*;
 
 /// Parse a number format string
@@ -13,7 +21,7 @@ use crate::types::*;
 /// * `input_str` - The format string to parse
 ///
 /// # Returns
-/// * `Result<NumberFormat, String>` - The parsing result, or an error message
+/// * `Result<NumberFormat, ParseError>` - The parsing result, or an error pointing at the offending input
 ///
 /// # Examples
 /// ```
@@ -21,101 +29,85 @@ use crate::types::*;
 ///
 /// let result = parse_number_format("0.00").unwrap();
 /// ```
-pub fn parse_number_format(input_str: &str) -> Result<NumberFormat, String> {
-    // Handle "General" format as a special case
-    // If the input string CONTAINS "general" (case-insensitive), treat it as General format.
-    if input_str.to_lowercase().contains("general") {
-        let general_section = FormatSection {
-            tokens: vec![FormatToken::GeneralNumeric],
-            color: None,
-            condition: None,
-            is_text_section: false,
-            num_scaling_commas: 0,
-            has_datetime: false,
-            has_text_format: false,
-            has_fraction: false,
-            fixed_denominator: None,
-            num_integer_part_tokens: 0,
-            num_fractional_part_tokens: 0,
-        };
-
-        let general_text_section = FormatSection {
-            tokens: vec![FormatToken::TextValue], // @ token
-            color: None,
-            condition: None,
-            is_text_section: true,
-            num_scaling_commas: 0,
-            has_datetime: false,
-            has_text_format: true,
-            has_fraction: false,
-            fixed_denominator: None,
-            num_integer_part_tokens: 0,
-            num_fractional_part_tokens: 0,
-        };
-
-        return Ok(NumberFormat {
-            positive_section: general_section,
-            negative_section: None,
-            zero_section: None,
-            text_section: Some(general_text_section),
-        });
-    }
+pub fn parse_number_format(input_str: &str) -> Result<NumberFormat, ParseError> {
+    parse_number_format_with_options(input_str, &ParseOptions::default())
+}
 
+/// Parse a number format string with options such as the extended token set
+///
+/// # Examples
+/// ```
+/// use number_format::parser::parse_number_format_with_options;
+/// use number_format::types::ParseOptions;
+///
+/// let options = ParseOptions::default().with_extended_tokens(true);
+/// let result = parse_number_format_with_options("Q yyyy", &options).unwrap();
+/// assert_eq!(result.to_format_code(), "Q yyyy");
+/// ```
+pub fn parse_number_format_with_options(
+    input_str: &str,
+    options: &ParseOptions,
+) -> Result<NumberFormat, ParseError> {
     let mut input = input_str;
+    let offset = |remaining: &str| input_str.len() - remaining.len();
 
-    let make_err_msg = |e, remaining: &str| -> String {
-        format!("Parse error: {e:?} at remaining input '{remaining}'")
-    };
-
-    let mut positive_section = parse_one_section(0)
-        .parse_next(&mut input)
-        .map_err(|e| make_err_msg(e, input))?;
+    // Parse up to four sections, remembering where each one starts and ends
+    let mut sections: Vec<FormatSection> = Vec::new();
+    let mut spans: Vec<Range<usize>> = Vec::new();
+    loop {
+        let section_index = sections.len();
+        let start = offset(input);
+        if section_index == 4 {
+            // Point at the separator that opened the fifth section, after the text section
+            return Err(ParseError::TooManySections {
+                section: 3,
+                span: start - 1..input_str.len(),
+            });
+        }
 
-    let mut negative_section = None;
-    if input.starts_with(';') {
-        literal(";")
+        let section = parse_one_section(section_index, *options)
             .parse_next(&mut input)
-            .map_err(ErrMode::Backtrack)
-            .map_err(|e| make_err_msg(e, input))?;
-        negative_section = Some(
-            parse_one_section(1)
-                .parse_next(&mut input)
-                .map_err(|e| make_err_msg(e, input))?,
-        );
-    }
+            .map_err(|_| classify_token_error(input_str, offset(input), section_index))?;
+        sections.push(section);
+        spans.push(start..offset(input));
 
-    let mut zero_section = None;
-    if input.starts_with(';') {
-        literal(";")
+        if literal::<_, _, ContextError>(";")
             .parse_next(&mut input)
-            .map_err(ErrMode::Backtrack)
-            .map_err(|e| make_err_msg(e, input))?;
-        zero_section = Some(
-            parse_one_section(2)
-                .parse_next(&mut input)
-                .map_err(|e| make_err_msg(e, input))?,
-        );
+            .is_err()
+        {
+            break;
+        }
     }
 
-    let mut text_section = None;
-    if input.starts_with(';') {
-        literal(";")
-            .parse_next(&mut input)
-            .map_err(ErrMode::Backtrack)
-            .map_err(|e| make_err_msg(e, input))?;
-        text_section = Some(
-            parse_one_section(3)
-                .parse_next(&mut input)
-                .map_err(|e| make_err_msg(e, input))?,
-        );
+    // `g` and `e` are era tokens only in date sections, as in Excel
+    for (index, section) in sections.iter().enumerate() {
+        if is_numeric_with_era(section) {
+            let span = token_span(input_str, spans[index].clone(), options, is_era_token);
+            return Err(ParseError::UnexpectedCharacter {
+                section: index,
+                found: input_str[span.start..].chars().next().unwrap_or('\0'),
+                span,
+            });
+        }
     }
 
-    if !input.is_empty() {
-        return Err(format!(
-            "Too many sections or trailing characters: '{input}'"
-        ));
+    if let Some(found) = input.chars().next() {
+        let pos = offset(input);
+        return Err(ParseError::UnexpectedCharacter {
+            section: sections.len() - 1,
+            span: pos..pos + found.len_utf8(),
+            found,
+        });
     }
 
+    let mut sections = sections.into_iter();
+    let Some(mut positive_section) = sections.next() else {
+        unreachable!("the first section is always parsed");
+    };
+    let mut negative_section = sections.next();
+    let mut zero_section = sections.next();
+    let mut text_section = sections.next();
+
     // Resolve month/minute ambiguity in all sections
     resolve_month_minute_ambiguity_in_section(&mut positive_section.tokens);
     if let Some(ref mut section) = negative_section {
@@ -127,38 +119,44 @@ pub fn parse_number_format(input_str: &str) -> Result<NumberFormat, String> {
     if let Some(ref mut section) = text_section {
         resolve_month_minute_ambiguity_in_section(&mut section.tokens);
         if section.condition.is_some() {
-            return Err("Text section (4th) must not have a condition.".to_string());
+            return Err(ParseError::ConditionInTextSection {
+                section: 3,
+                span: condition_span(input_str, spans[3].start),
+            });
         }
     }
 
     // Validate condition constraints
-    let mut condition_count = 0;
-    if positive_section.condition.is_some() {
-        condition_count += 1;
-    }
-    if negative_section
-        .as_ref()
-        .is_some_and(|s| s.condition.is_some())
-    {
-        condition_count += 1;
-    }
-    if zero_section.as_ref().is_some_and(|s| s.condition.is_some()) {
-        condition_count += 1;
-    }
+    let conditional_sections: Vec<usize> = [
+        Some(&positive_section),
+        negative_section.as_ref(),
+        zero_section.as_ref(),
+    ]
+    .iter()
+    .enumerate()
+    .filter(|(_, s)| s.is_some_and(|s| s.condition.is_some()))
+    .map(|(i, _)| i)
+    .collect();
 
-    if condition_count > 2 {
-        return Err("Format string cannot have more than two conditional sections.".to_string());
+    if conditional_sections.len() > 2 {
+        let section = conditional_sections[2];
+        return Err(ParseError::TooManyConditions {
+            section,
+            span: condition_span(input_str, spans[section].start),
+        });
     }
 
-    // Validate text section
-    if let Some(ref section) = text_section {
-        for token in &section.tokens {
-            if token.is_numeric_or_date() {
-                return Err(format!(
-                    "Text section (4th) contains a numeric or date symbol: {token:?}"
-                ));
-            }
-        }
+    // Validate text section; General is allowed there and shows the text like `@`
+    if let Some(ref section) = text_section
+        && section
+            .tokens
+            .iter()
+            .any(|t| t.is_numeric_or_date() && *t != FormatToken::GeneralNumeric)
+    {
+        return Err(ParseError::NumericTokenInTextSection {
+            section: 3,
+            span: numeric_token_span(input_str, spans[3].clone(), options),
+        });
     }
 
     Ok(NumberFormat {
@@ -168,3 +166,139 @@ pub fn parse_number_format(input_str: &str) -> Result<NumberFormat, String> {
         text_section,
     })
 }
+
+/// Work out why no token could be parsed at byte `pos` of `input_str`
+pub(super) fn classify_token_error(input_str: &str, pos: usize, section: usize) -> ParseError {
+    let rest = &input_str[pos..];
+    let Some(found) = rest.chars().next() else {
+        return ParseError::UnexpectedCharacter {
+            section,
+            span: pos..pos,
+            found: '\0',
+        };
+    };
+
+    match found {
+        // Quoted text only fails when the closing quote is missing
+        '"' => ParseError::UnterminatedQuote {
+            section,
+            span: pos..input_str.len(),
+        },
+        '[' => match rest.find(']') {
+            None => ParseError::UnterminatedBracket {
+                section,
+                span: pos..input_str.len(),
+            },
+            Some(close) => {
+                let span = pos..pos + close + 1;
+                let is_condition = rest[1..].starts_with(['<', '>', '=']);
+                if is_condition && section == 3 {
+                    ParseError::ConditionInTextSection { section, span }
+                } else if is_condition {
+                    ParseError::InvalidCondition { section, span }
+                } else {
+                    ParseError::UnknownBracketTag { section, span }
+                }
+            }
+        },
+        // These only fail when nothing follows them
+        '\\' | '*' | '_' => ParseError::DanglingModifier {
+            section,
+            span: pos..input_str.len(),
+        },
+        _ => ParseError::UnexpectedCharacter {
+            section,
+            span: pos..pos + found.len_utf8(),
+            found,
+        },
+    }
+}
+
+/// Byte range of the `[...]` condition at the start of a section
+fn condition_span(input_str: &str, section_start: usize) -> Range<usize> {
+    let end = input_str[section_start..]
+        .find(']')
+        .map_or(input_str.len(), |close| section_start + close + 1);
+    section_start..end
+}
+
+/// Byte range of the first numeric or date token in a section, or the whole section if none is found
+fn numeric_token_span(
+    input_str: &str,
+    section_span: Range<usize>,
+    options: &ParseOptions,
+) -> Range<usize> {
+    token_span(input_str, section_span, options, |token| {
+        token.is_numeric_or_date() && *token != FormatToken::GeneralNumeric
+    })
+}
+
+/// Byte range of the first token in a section that satisfies `predicate`, or the whole section
+fn token_span(
+    input_str: &str,
+    section_span: Range<usize>,
+    options: &ParseOptions,
+    predicate: impl Fn(&FormatToken) -> bool,
+) -> Range<usize> {
+    let mut rest = &input_str[section_span.clone()];
+    // Tokenize as the section parser does, past any condition
+    let _ = opt(parse_condition).parse_next(&mut rest);
+    let mut previous = None;
+    let mut in_denominator = false;
+    while !rest.is_empty() {
+        let token_start = section_span.end - rest.len();
+        let denominator_digit =
+            parse_denominator_digit(&mut rest, previous.as_ref(), in_denominator);
+        in_denominator = denominator_digit.is_some();
+        let token = match denominator_digit {
+            Some(digit) => digit,
+            None => match parse_token(*options).parse_next(&mut rest) {
+                Ok(token) => token,
+                Err(_) => break,
+            },
+        };
+        if predicate(&token) {
+            return token_start..section_span.end - rest.len();
+        }
+        previous = Some(token);
+    }
+    section_span
+}
+
+fn is_era_token(token: &FormatToken) -> bool {
+    matches!(
+        token,
+        FormatToken::EraNameShort
+            | FormatToken::EraNameAbbr
+            | FormatToken::EraNameFull
+            | FormatToken::EraYear
+            | FormatToken::EraYearPadded
+    )
+}
+
+/// Whether a number section picked up era tokens, as `0e` or `0.0E0` would
+///
+/// A section whose only date tokens are eras is a number section if it has
+/// number placeholders, General, a percent sign or an exponent.
+fn is_numeric_with_era(section: &FormatSection) -> bool {
+    let mut has_era = false;
+    let mut has_number = false;
+    for token in &section.tokens {
+        if is_era_token(token) {
+            has_era = true;
+        } else if token.is_datetime_placeholder() {
+            return false;
+        } else if matches!(
+            token,
+            FormatToken::DigitOrZero
+                | FormatToken::DigitIfNeeded
+                | FormatToken::DigitOrSpace
+                | FormatToken::GeneralNumeric
+                | FormatToken::Percentage
+                | FormatToken::Exponential(_)
+        ) {
+            has_number = true;
+        }
+    }
+    has_era && has_number
+}
@@ -1,11 +1,17 @@
 //! Number format parsing module
 //!
 //! This module is responsible for parsing number format strings and converting them into internal TokenTree representation.
-//! The main entry point is the `parse_number_format` function.
+//! The main entry point is the `parse_number_format` function; `NumberFormat::to_format_code` converts a parsed format back.
+//! `parse_cst` produces a lossless concrete syntax tree for editors and syntax highlighting.
 
 mod combinators;
+mod cst;
+mod error;
 mod format;
 mod sections;
+mod serialize;
 mod tokens;
 
-pub use format::parse_number_format;
+pub use cst::{CstToken, CstTokenKind, FormatCst, parse_cst, parse_cst_with_options};
+pub use error::ParseError;
+pub use format::{parse_number_format, parse_number_format_with_options};
@@ -8,89 +8,150 @@ use crate::parser::combinators::parse_condition;
 use crate::parser::tokens::*;
 use crate::types::*;
 
+/// Parse a single format token
+pub fn parse_format_token(input: &mut &str) -> ModalResult<FormatToken> {
+    let date_tokens = alt((
+        parse_year_four_digit,      // Then yyyy
+        parse_year_two_digit,       // Then yy
+        parse_month_full_name_long, // Then mmmmmm
+        parse_month_letter,         // Then mmmmm
+        parse_month_full_name,      // Then mmmm
+        parse_month_abbr,           // Then mmm
+        // mm and m are handled after more specific t
+        parse_day_full_name, // Then dddd
+        parse_day_abbr,      // Then ddd
+        parse_day_padded,    // Then dd
+        parse_day_single,    // Then d
+        parse_era_name,      // ggg, gg, g
+        parse_era_year,      // ee, e (not E+/E-)
+        parse_calendar,      // B1/B2
+    ));
+
+    let time_tokens = alt((
+        parse_elapsed_hours_padded,   // [hh] (must be before [h])
+        parse_elapsed_minutes_padded, // [mm] (must be before [m])
+        parse_elapsed_seconds_padded, // [ss] (must be before [s])
+        parse_elapsed_hours,          // [h]
+        parse_elapsed_minutes,        // [m]
+        parse_elapsed_seconds,        // [s]
+        parse_hour_padded,            // hh
+        parse_second_padded,          // ss (try before single m/s)
+        parse_month_or_minute_padded, // mm (general, resolved later)
+        parse_hour_single,            // h
+        parse_second_single,          // s (try before single m)
+        parse_month_or_minute_single, // m (general, resolved later)
+        parse_am_pm,
+        parse_a_p,
+    ));
+
+    let number_tokens = alt((
+        parse_digit_or_zero,
+        parse_digit_if_needed,
+        parse_digit_or_space,
+        parse_decimal_point,
+        parse_thousands_separator,
+        parse_literal_percentage_sign,
+        parse_percentage,
+        parse_locale_currency_symbol,
+        parse_exponential,
+    ));
+
+    let text_special_tokens = alt((
+        parse_text_value_token,
+        parse_escaped_char_as_literal,
+        parse_fill,
+        parse_skip_width,
+        parse_quoted_text,
+        parse_color,
+        parse_dbnum,
+        parse_literal_passthrough, // Should be last in this group
+    ));
+
+    alt((
+        parse_general,
+        date_tokens,
+        time_tokens,
+        number_tokens,
+        text_special_tokens,
+    ))
+    .parse_next(input)
+}
+
+/// Parse a single token of the dialect selected by the options
+///
+/// With `extended_tokens`, the extended dialect is tried before Excel's tokens.
+pub fn parse_token(options: ParseOptions) -> impl FnMut(&mut &str) -> ModalResult<FormatToken> {
+    move |input: &mut &str| {
+        if options.extended_tokens {
+            alt((parse_extended_token, parse_format_token)).parse_next(input)
+        } else {
+            parse_format_token.parse_next(input)
+        }
+    }
+}
+
 /// Parse a sequence of tokens
-pub fn parse_section_tokens() -> impl FnMut(&mut &str) -> ModalResult<Vec<FormatToken>> {
+pub fn parse_section_tokens(
+    options: ParseOptions,
+) -> impl FnMut(&mut &str) -> ModalResult<Vec<FormatToken>> {
     move |input: &mut &str| {
         // This inner logic IS parse_section_tokens_direct
         let mut parts: Vec<FormatToken> = vec![];
+        let mut in_denominator = false;
         while !input.is_empty() && !lookahead_for_section_separator(input) {
-            let date_tokens = alt((
-                parse_year_four_digit,      // Then yyyy
-                parse_year_two_digit,       // Then yy
-                parse_month_full_name_long, // Then mmmmmm
-                parse_month_letter,         // Then mmmmm
-                parse_month_full_name,      // Then mmmm
-                parse_month_abbr,           // Then mmm
-                // mm and m are handled after more specific t
-                parse_day_full_name, // Then dddd
-                parse_day_abbr,      // Then ddd
-                parse_day_padded,    // Then dd
-                parse_day_single,    // Then d
-            ));
-
-            let time_tokens = alt((
-                parse_elapsed_hours_padded,   // [hh] (must be before [h])
-                parse_elapsed_minutes_padded, // [mm] (must be before [m])
-                parse_elapsed_seconds_padded, // [ss] (must be before [s])
-                parse_elapsed_hours,          // [h]
-                parse_elapsed_minutes,        // [m]
-                parse_elapsed_seconds,        // [s]
-                parse_hour_padded,            // hh
-                parse_second_padded,          // ss (try before single m/s)
-                parse_month_or_minute_padded, // mm (general, resolved later)
-                parse_hour_single,            // h
-                parse_second_single,          // s (try before single m)
-                parse_month_or_minute_single, // m (general, resolved later)
-                parse_am_pm,
-                parse_a_p,
-            ));
-
-            let number_tokens = alt((
-                parse_digit_or_zero,
-                parse_digit_if_needed,
-                parse_digit_or_space,
-                parse_decimal_point,
-                parse_thousands_separator,
-                parse_literal_percentage_sign,
-                parse_percentage,
-                parse_locale_currency_symbol,
-                parse_exponential,
-            ));
-
-            let text_special_tokens = alt((
-                parse_text_value_token,
-                parse_escaped_char_as_literal,
-                parse_fill,
-                parse_skip_width,
-                parse_quoted_text,
-                parse_color,
-                parse_literal_passthrough, // Should be last in this group
-            ));
-
-            let token = alt((date_tokens, time_tokens, number_tokens, text_special_tokens))
-                .parse_next(input)?;
+            let denominator_digit = parse_denominator_digit(input, parts.last(), in_denominator);
+            in_denominator = denominator_digit.is_some();
+            let token = match denominator_digit {
+                Some(digit) => digit,
+                None => parse_token(options).parse_next(input)?,
+            };
             parts.push(token);
         }
 
-        // Use the more detailed ambiguity resolution function
-        resolve_month_minute_ambiguity_in_section(&mut parts);
+        normalize_section_tokens(&mut parts);
+        Ok(parts)
+    }
+}
 
-        // Comma transformation logic for datetime sections
-        let is_datetime_section = parts.iter().any(FormatToken::is_datetime_placeholder);
-        if is_datetime_section {
-            for token in parts.iter_mut() {
-                if matches!(token, FormatToken::ThousandsSeparator) {
-                    *token = FormatToken::LiteralChar(',');
-                }
+/// Parse a digit of a fixed denominator, such as the `16` of `# ?/16`
+///
+/// Digits are placeholders or errors elsewhere, so a denominator starts with 1-9
+/// right after the slash and then takes every digit that follows.
+pub fn parse_denominator_digit(
+    input: &mut &str,
+    previous: Option<&FormatToken>,
+    in_denominator: bool,
+) -> Option<FormatToken> {
+    let digit = input.chars().next()?;
+    let starts = previous == Some(&FormatToken::LiteralChar('/')) && matches!(digit, '1'..='9');
+    let continues = in_denominator && digit.is_ascii_digit();
+    if !starts && !continues {
+        return None;
+    }
+    *input = &input[1..];
+    Some(FormatToken::LiteralChar(digit))
+}
+
+/// Apply the context-dependent rewrites to a freshly tokenized section
+pub fn normalize_section_tokens(parts: &mut Vec<FormatToken>) {
+    // Use the more detailed ambiguity resolution function
+    resolve_month_minute_ambiguity_in_section(parts);
+
+    // Comma transformation logic for datetime sections
+    let is_datetime_section = parts.iter().any(FormatToken::is_datetime_placeholder);
+    if is_datetime_section {
+        for token in parts.iter_mut() {
+            if matches!(token, FormatToken::ThousandsSeparator) {
+                *token = FormatToken::LiteralChar(',');
             }
         }
-        Ok(parts)
     }
 }
 
 /// Parse a single format section
 pub fn parse_one_section(
     section_index: usize,
+    options: ParseOptions,
 ) -> impl FnMut(&mut &str) -> ModalResult<FormatSection> {
     move |input: &mut &str| {
         let is_text_s = section_index == 3;
@@ -102,7 +163,7 @@ pub fn parse_one_section(
         };
 
         // Parse all tokens initially, including all commas as ThousandsSeparator
-        let all_tokens: Vec<FormatToken> = (parse_section_tokens().parse_next(input))?;
+        let all_tokens: Vec<FormatToken> = (parse_section_tokens(options).parse_next(input))?;
 
         // Separate color token if present
         let (color_opt, mut tokens_after_color) = if !all_tokens.is_empty() {
@@ -0,0 +1,261 @@
+//! Serialization of parsed formats back to format code strings
+//!
+//! The emitted code is canonical and in the form Excel reads: literals that
+//! would otherwise be read as placeholders are escaped, quoted text is
+//! re-quoted with any `"` or `\` escaped outside the quotes, fixed denominators
+//! are written as in `# ?/16`, and colors, conditions, locale currency tags and
+//! scaling commas are re-emitted where the parser expects them. Parsing the
+//! output yields the same `NumberFormat` again, except that quoted text
+//! containing `"` or `\` comes back split around those characters.
+
+use std::fmt;
+
+use crate::types::*;
+
+/// Literal characters that the parser accepts without escaping
+const PASSTHROUGH_CHARS: [char; 18] = [
+    '$', '+', '(', ':', '^', '\'', '{', '<', '=', '-', '/', ')', '!', '&', '~', '}', '>', ' ',
+];
+
+impl NumberFormat {
+    /// Convert the format back to a format code string
+    ///
+    /// # Examples
+    /// ```
+    /// use number_format::parser::parse_number_format;
+    ///
+    /// let mut format = parse_number_format("#,##0;[Red]-#,##0").unwrap();
+    /// format.positive_section.color = Some(number_format::types::ColorType::Blue);
+    /// assert_eq!(format.to_format_code(), "[Blue]#,##0;[Red]-#,##0");
+    /// ```
+    pub fn to_format_code(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl fmt::Display for NumberFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.positive_section)?;
+
+        // Emit every section up to the last one present; a missing section in
+        // between has to be written as an empty one to keep the positions.
+        let rest = [
+            self.negative_section.as_ref(),
+            self.zero_section.as_ref(),
+            self.text_section.as_ref(),
+        ];
+        let count = rest.iter().rposition(Option::is_some).map_or(0, |i| i + 1);
+        for section in &rest[..count] {
+            f.write_str(";")?;
+            if let Some(section) = section {
+                write!(f, "{}", section)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FormatSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(condition) = &self.condition {
+            write!(f, "{}", condition)?;
+        }
+        if let Some(color) = &self.color {
+            write!(f, "[{}]", color)?;
+        }
+
+        let is_datetime = self.tokens.iter().any(FormatToken::is_datetime_placeholder);
+
+        // Scaling commas follow the last numeric token, or lead the section if there is none
+        let last_numeric_idx = self.tokens.iter().rposition(|t| {
+            matches!(
+                t,
+                FormatToken::DigitOrZero
+                    | FormatToken::DigitIfNeeded
+                    | FormatToken::DigitOrSpace
+                    | FormatToken::DecimalPoint
+                    | FormatToken::Exponential(_)
+            )
+        });
+        let scaling_commas = ",".repeat(self.num_scaling_commas as usize);
+        if last_numeric_idx.is_none() {
+            f.write_str(&scaling_commas)?;
+        }
+
+        // A fixed denominator (#/16) follows the last digit placeholder
+        let last_placeholder_idx = self.tokens.iter().rposition(|t| {
+            matches!(
+                t,
+                FormatToken::DigitOrZero | FormatToken::DigitIfNeeded | FormatToken::DigitOrSpace
+            )
+        });
+
+        for (i, token) in self.tokens.iter().enumerate() {
+            let after_era_year = i > 0
+                && matches!(
+                    self.tokens[i - 1],
+                    FormatToken::EraYear | FormatToken::EraYearPadded
+                );
+            match token {
+                // Commas in date/time sections are parsed back into literals
+                FormatToken::LiteralChar(',') if is_datetime => f.write_str(",")?,
+                // `e+` would be read back as an exponent
+                FormatToken::LiteralChar(c @ ('+' | '-')) if after_era_year => {
+                    write!(f, "\\{}", c)?
+                }
+                _ => write!(f, "{}", token)?,
+            }
+            if Some(i) == last_placeholder_idx
+                && let Some(denominator) = self.fixed_denominator
+            {
+                write!(f, "/{}", denominator)?;
+            }
+            if Some(i) == last_numeric_idx {
+                f.write_str(&scaling_commas)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for FormatToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormatToken::DigitOrZero => f.write_str("0"),
+            FormatToken::DigitIfNeeded => f.write_str("#"),
+            FormatToken::DigitOrSpace => f.write_str("?"),
+            FormatToken::DecimalPoint => f.write_str("."),
+            FormatToken::ThousandsSeparator => f.write_str(","),
+            FormatToken::Percentage => f.write_str("%"),
+            FormatToken::Exponential(ExponentialNotation::Plus) => f.write_str("E+"),
+            FormatToken::Exponential(ExponentialNotation::Minus) => f.write_str("E-"),
+            FormatToken::LiteralChar(c) if PASSTHROUGH_CHARS.contains(c) => write!(f, "{}", c),
+            FormatToken::LiteralChar(c) => write!(f, "\\{}", c),
+            FormatToken::Fill(c) => write!(f, "*{}", c),
+            FormatToken::SkipWidth(c) => write!(f, "_{}", c),
+            // Excel ends quoted text at any quote and reads a backslash in it
+            // as itself, so both are written escaped between quoted runs
+            FormatToken::QuotedText(text) if text.is_empty() => f.write_str("\"\""),
+            FormatToken::QuotedText(text) => {
+                let mut quoted = false;
+                for c in text.chars() {
+                    if c == '"' || c == '\\' {
+                        if quoted {
+                            f.write_str("\"")?;
+                            quoted = false;
+                        }
+                        write!(f, "\\{}", c)?;
+                    } else {
+                        if !quoted {
+                            f.write_str("\"")?;
+                            quoted = true;
+                        }
+                        write!(f, "{}", c)?;
+                    }
+                }
+                if quoted {
+                    f.write_str("\"")?;
+                }
+                Ok(())
+            }
+            FormatToken::TextValue => f.write_str("@"),
+            FormatToken::Color(color) => write!(f, "[{}]", color),
+            FormatToken::YearTwoDigit => f.write_str("yy"),
+            FormatToken::YearFourDigit => f.write_str("yyyy"),
+            FormatToken::MonthNum | FormatToken::MinuteNum | FormatToken::MonthOrMinute1 => {
+                f.write_str("m")
+            }
+            FormatToken::MonthNumPadded
+            | FormatToken::MinuteNumPadded
+            | FormatToken::MonthOrMinute2 => f.write_str("mm"),
+            FormatToken::MonthAbbr => f.write_str("mmm"),
+            FormatToken::MonthFullName => f.write_str("mmmm"),
+            FormatToken::MonthLetter => f.write_str("mmmmm"),
+            FormatToken::DayNum => f.write_str("d"),
+            FormatToken::DayNumPadded => f.write_str("dd"),
+            FormatToken::WeekdayAbbr => f.write_str("ddd"),
+            FormatToken::WeekdayFullName => f.write_str("dddd"),
+            FormatToken::EraNameShort => f.write_str("g"),
+            FormatToken::EraNameAbbr => f.write_str("gg"),
+            FormatToken::EraNameFull => f.write_str("ggg"),
+            FormatToken::EraYear => f.write_str("e"),
+            FormatToken::EraYearPadded => f.write_str("ee"),
+            FormatToken::Quarter => f.write_str("Q"),
+            FormatToken::QuarterName => f.write_str("QQ"),
+            FormatToken::IsoWeek => f.write_str("WW"),
+            FormatToken::IsoWeekYear => f.write_str("IYYY"),
+            FormatToken::DayOfYear => f.write_str("j"),
+            FormatToken::DayOfYearPadded => f.write_str("jjj"),
+            FormatToken::Hour12Or24 => f.write_str("h"),
+            FormatToken::Hour12Or24Padded => f.write_str("hh"),
+            FormatToken::SecondNum => f.write_str("s"),
+            FormatToken::SecondNumPadded => f.write_str("ss"),
+            FormatToken::AmPm(AmPmStyle::UpperCase) => f.write_str("AM/PM"),
+            FormatToken::AmPm(AmPmStyle::LowerCase) => f.write_str("am/pm"),
+            FormatToken::AP(AmPmStyle::UpperCase) => f.write_str("A/P"),
+            FormatToken::AP(AmPmStyle::LowerCase) => f.write_str("a/p"),
+            FormatToken::ElapsedHours => f.write_str("[h]"),
+            FormatToken::ElapsedMinutes => f.write_str("[m]"),
+            FormatToken::ElapsedSeconds => f.write_str("[s]"),
+            FormatToken::ElapsedHoursPadded => f.write_str("[hh]"),
+            FormatToken::ElapsedMinutesPadded => f.write_str("[mm]"),
+            FormatToken::ElapsedSecondsPadded => f.write_str("[ss]"),
+            // Excel's spelling, which `¤` is shorthand for
+            FormatToken::CurrencySymbolLocaleDefault => f.write_str("[$]"),
+            FormatToken::CurrencySymbolLocalePrefixed(value) => {
+                // Stored as "<prefix>:[$-<code>]"
+                match value.rfind(":[$-") {
+                    Some(idx) => {
+                        let prefix = &value[..idx];
+                        let code = value[idx + 4..].trim_end_matches(']');
+                        write!(f, "[${}-{}]", prefix, code)
+                    }
+                    None => write!(f, "[${}]", value),
+                }
+            }
+            FormatToken::LocaleTag(code) => write!(f, "[$-{}]", code),
+            FormatToken::GeneralNumeric => f.write_str("General"),
+            FormatToken::Calendar(CalendarType::Gregorian) => f.write_str("B1"),
+            FormatToken::Calendar(CalendarType::Hijri) => f.write_str("B2"),
+            FormatToken::Calendar(CalendarType::ThaiBuddhist) => f.write_str("[$-70000]"),
+            FormatToken::DbNum(DbNumStyle::DbNum1) => f.write_str("[DBNum1]"),
+            FormatToken::DbNum(DbNumStyle::DbNum2) => f.write_str("[DBNum2]"),
+            FormatToken::DbNum(DbNumStyle::DbNum3) => f.write_str("[DBNum3]"),
+        }
+    }
+}
+
+impl fmt::Display for Condition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}{}]", self.operator, self.value)
+    }
+}
+
+impl fmt::Display for ComparisonOperator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ComparisonOperator::Eq => "=",
+            ComparisonOperator::Gt => ">",
+            ComparisonOperator::Lt => "<",
+            ComparisonOperator::Ge => ">=",
+            ComparisonOperator::Le => "<=",
+            ComparisonOperator::Ne => "<>",
+        })
+    }
+}
+
+impl fmt::Display for ColorType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ColorType::Red => f.write_str("Red"),
+            ColorType::Green => f.write_str("Green"),
+            ColorType::Blue => f.write_str("Blue"),
+            ColorType::Magenta => f.write_str("Magenta"),
+            ColorType::Cyan => f.write_str("Cyan"),
+            ColorType::Yellow => f.write_str("Yellow"),
+            ColorType::Black => f.write_str("Black"),
+            ColorType::White => f.write_str("White"),
+            ColorType::Indexed(index) => write!(f, "Color{}", index),
+        }
+    }
+}
@@ -1,5 +1,5 @@
-use winnow::ascii::Caseless;
-use winnow::combinator::{alt, delimited, preceded, repeat};
+use winnow::ascii::{Caseless, dec_uint};
+use winnow::combinator::{alt, delimited, not, preceded, repeat, terminated};
 use winnow::error::{ContextError, ErrMode};
 use winnow::token::{any, literal, none_of, one_of};
 use winnow::{ModalResult, Parser};
@@ -94,6 +94,54 @@ pub fn parse_day_single(input: &mut &str) -> ModalResult<FormatToken> {
         .map_err(ErrMode::Backtrack)
 }
 
+pub fn parse_era_name(input: &mut &str) -> ModalResult<FormatToken> {
+    alt((
+        repeat::<_, _, (), ContextError, _>(3.., one_of(('g', 'G')).map(|_| ()))
+            .value(FormatToken::EraNameFull),
+        literal(Caseless("gg")).value(FormatToken::EraNameAbbr),
+        literal(Caseless("g")).value(FormatToken::EraNameShort),
+    ))
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
+}
+
+pub fn parse_era_year(input: &mut &str) -> ModalResult<FormatToken> {
+    // `E+` and `E-` are exponents
+    terminated(
+        alt((
+            repeat::<_, _, (), ContextError, _>(2.., one_of(('e', 'E')).map(|_| ()))
+                .value(FormatToken::EraYearPadded),
+            literal(Caseless("e")).value(FormatToken::EraYear),
+        )),
+        not(one_of(['+', '-'])),
+    )
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
+}
+
+pub fn parse_calendar(input: &mut &str) -> ModalResult<FormatToken> {
+    alt((
+        literal(Caseless("B1")).value(FormatToken::Calendar(CalendarType::Gregorian)),
+        literal(Caseless("B2")).value(FormatToken::Calendar(CalendarType::Hijri)),
+    ))
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
+}
+
+/// Tokens of the extended dialect: quarter, ISO week, ISO week-year and day of year
+pub fn parse_extended_token(input: &mut &str) -> ModalResult<FormatToken> {
+    alt((
+        literal(Caseless("QQ")).value(FormatToken::QuarterName),
+        literal(Caseless("Q")).value(FormatToken::Quarter),
+        literal(Caseless("WW")).value(FormatToken::IsoWeek),
+        literal(Caseless("IYYY")).value(FormatToken::IsoWeekYear),
+        literal(Caseless("jjj")).value(FormatToken::DayOfYearPadded),
+        literal(Caseless("j")).value(FormatToken::DayOfYear),
+    ))
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
+}
+
 // Time related parsers
 pub fn parse_hour_padded(input: &mut &str) -> ModalResult<FormatToken> {
     repeat::<_, _, (), ContextError, _>(2.., one_of(('h', 'H')).map(|_| ()))
@@ -243,6 +291,14 @@ pub fn parse_exponential(input: &mut &str) -> ModalResult<FormatToken> {
     .map_err(ErrMode::Backtrack)
 }
 
+// General format parser
+pub fn parse_general(input: &mut &str) -> ModalResult<FormatToken> {
+    literal(Caseless("General"))
+        .value(FormatToken::GeneralNumeric)
+        .parse_next(input)
+        .map_err(ErrMode::Backtrack)
+}
+
 // Text and special character parsers
 pub fn parse_text_value_token(input: &mut &str) -> ModalResult<FormatToken> {
     literal("@")
@@ -302,9 +358,28 @@ pub fn parse_color(input: &mut &str) -> ModalResult<FormatToken> {
     let black = literal(Caseless("[Black]")).value(FormatToken::Color(ColorType::Black));
     let white = literal(Caseless("[White]")).value(FormatToken::Color(ColorType::White));
 
-    alt((red, green, blue, magenta, cyan, yellow, black, white))
-        .parse_next(input)
-        .map_err(ErrMode::Backtrack)
+    let indexed = delimited(
+        literal(Caseless("[Color")),
+        dec_uint.verify(|index: &u8| (1..=56).contains(index)),
+        literal("]"),
+    )
+    .map(|index| FormatToken::Color(ColorType::Indexed(index)));
+
+    alt((
+        red, green, blue, magenta, cyan, yellow, black, white, indexed,
+    ))
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
+}
+
+pub fn parse_dbnum(input: &mut &str) -> ModalResult<FormatToken> {
+    alt((
+        literal(Caseless("[DBNum1]")).value(FormatToken::DbNum(DbNumStyle::DbNum1)),
+        literal(Caseless("[DBNum2]")).value(FormatToken::DbNum(DbNumStyle::DbNum2)),
+        literal(Caseless("[DBNum3]")).value(FormatToken::DbNum(DbNumStyle::DbNum3)),
+    ))
+    .parse_next(input)
+    .map_err(ErrMode::Backtrack)
 }
 
 pub fn parse_locale_currency_symbol(input: &mut &str) -> ModalResult<FormatToken> {
@@ -315,30 +390,30 @@ pub fn parse_locale_currency_symbol(input: &mut &str) -> ModalResult<FormatToken
     .parse_next(input)
 }
 
-/// Parse Excel-style locale currency format like [$-409] or [$-zh-TW] or [$US-409]
+/// Parse Excel-style locale currency format like [$-409], [$-zh-TW], [$US-409] or [$€]
 pub fn parse_excel_locale_currency_format(input: &mut &str) -> ModalResult<FormatToken> {
     // Parse the opening sequence [$
     literal("[$").parse_next(input)?;
 
-    // Check for currency prefix like US before the - sign
+    // Read the currency symbol, which ends at the dash before a locale code or at the bracket
     let mut currency_prefix = String::new();
-    let original_input = *input;
-
-    // Read characters until we hit a dash
-    while !input.is_empty() && !input.starts_with('-') {
+    while !input.is_empty() && !input.starts_with(['-', ']']) {
         let c = input.chars().next().unwrap();
         currency_prefix.push(c);
         *input = &input[c.len_utf8()..];
     }
 
-    // Parse the dash
+    // Without a dash there is no locale code, as in [$€]
     if literal::<_, _, ContextError>("-")
         .parse_next(input)
         .is_err()
     {
-        // No dash found, restore input and clear prefix
-        *input = original_input;
-        currency_prefix.clear();
+        literal("]").parse_next(input)?;
+        return Ok(if currency_prefix.is_empty() {
+            FormatToken::CurrencySymbolLocaleDefault
+        } else {
+            FormatToken::CurrencySymbolLocalePrefixed(currency_prefix)
+        });
     }
 
     // Now parse the locale code which can be:
@@ -368,8 +443,10 @@ pub fn parse_excel_locale_currency_format(input: &mut &str) -> ModalResult<Forma
             "{}:{}",
             currency_prefix, full_code
         )))
+    } else if !locale_code.is_empty() {
+        // A bare locale tag selects the locale (and calendar) of date sections
+        Ok(FormatToken::LocaleTag(locale_code))
     } else {
-        // Just store the locale code for using the default currency symbol of that locale
         Ok(FormatToken::CurrencySymbolLocaleDefault)
     }
 }
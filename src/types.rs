@@ -56,6 +56,16 @@ pub enum FormatToken {
     WeekdayAbbr,
     /// Full weekday name, e.g., Monday (dddd)
     WeekdayFullName,
+    /// Japanese era initial, e.g., R for Reiwa (g)
+    EraNameShort,
+    /// Japanese era name abbreviated to one character, e.g., 令 (gg)
+    EraNameAbbr,
+    /// Full Japanese era name, e.g., 令和 (ggg)
+    EraNameFull,
+    /// Year of the Japanese era (e)
+    EraYear,
+    /// Zero-padded year of the Japanese era (ee)
+    EraYearPadded,
     /// Hour in 12 or 24-hour format (h)
     Hour12Or24,
     /// Zero-padded hour in 12 or 24-hour format (hh)
@@ -90,12 +100,69 @@ pub enum FormatToken {
     /// Double m, might be month or minute, to be determined by context
     MonthOrMinute2,
 
-    /// Currency symbol for locale-dependent formatting
+    /// Currency symbol for locale-dependent formatting, written `[$]` or `¤`
     CurrencySymbolLocaleDefault,
-    /// Currency symbol with a specific prefix from format like [$US-409]
+    /// Currency symbol with a specific prefix from format like [$US-409], or [$€] without a code
     CurrencySymbolLocalePrefixed(String),
+    /// Locale tag without a currency symbol, like [$-407]; holds the code after the dash
+    LocaleTag(String),
     /// Represents the "General" format for numbers
     GeneralNumeric,
+    /// Calendar for the date tokens of the section, from `B1`/`B2` or the calendar byte of `[$-xxxxxxxx]`
+    Calendar(CalendarType),
+    /// CJK numerals for the digits of the section, from `[DBNum1]` to `[DBNum3]`
+    DbNum(DbNumStyle),
+
+    /// Quarter, e.g., Q3 (Q); extended tokens only
+    Quarter,
+    /// Quarter name from the locale, e.g., 3rd quarter (QQ); extended tokens only
+    QuarterName,
+    /// ISO 8601 week number, 1-53 (WW); extended tokens only
+    IsoWeek,
+    /// ISO 8601 week-based year, which differs from the year around New Year (IYYY); extended tokens only
+    IsoWeekYear,
+    /// Day of the year, 1-366 (j); extended tokens only
+    DayOfYear,
+    /// Zero-padded day of the year, 001-366 (jjj); extended tokens only
+    DayOfYearPadded,
+}
+
+/// Numerals that `[DBNum1]`, `[DBNum2]` and `[DBNum3]` substitute for ASCII digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DbNumStyle {
+    /// Ordinary numerals with positional units, e.g., 一百二十三 (`[DBNum1]`)
+    DbNum1,
+    /// Financial numerals with positional units, e.g., 壹佰贰拾叁 (`[DBNum2]`)
+    DbNum2,
+    /// Full-width digits, e.g., １２３ (`[DBNum3]`)
+    DbNum3,
+}
+
+/// Calendar used to display year, month and day
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CalendarType {
+    /// The Gregorian calendar (`B1`)
+    #[default]
+    Gregorian,
+    /// The tabular Hijri calendar used by Excel (`B2`, calendar type 06)
+    Hijri,
+    /// The Thai Buddhist calendar, 543 years ahead of the Gregorian one (calendar type 07)
+    ThaiBuddhist,
+}
+
+impl CalendarType {
+    /// Calendar selected by a locale code such as `1060000` in `[$-1060000]`
+    ///
+    /// The code is read as hexadecimal `NNCCLLLL`: number system, calendar type
+    /// and LCID. Returns `None` for codes without a calendar this crate renders.
+    pub fn from_locale_code(code: &str) -> Option<Self> {
+        let value = u32::from_str_radix(code, 16).ok()?;
+        match (value >> 16) & 0xFF {
+            0x06 => Some(CalendarType::Hijri),
+            0x07 => Some(CalendarType::ThaiBuddhist),
+            _ => None,
+        }
+    }
 }
 
 /// Represents the style (case) for AM/PM or A/P markers
@@ -126,6 +193,77 @@ pub enum ColorType {
     Black,
     /// White color
     White,
+    /// Color from the workbook palette, `[Color1]` to `[Color56]`
+    Indexed(u8),
+}
+
+impl ColorType {
+    /// 1-based position of the color in the 56-color palette
+    ///
+    /// The named colors are the first eight palette entries.
+    pub fn palette_index(&self) -> u8 {
+        match self {
+            ColorType::Black => 1,
+            ColorType::White => 2,
+            ColorType::Red => 3,
+            ColorType::Green => 4,
+            ColorType::Blue => 5,
+            ColorType::Yellow => 6,
+            ColorType::Magenta => 7,
+            ColorType::Cyan => 8,
+            ColorType::Indexed(index) => *index,
+        }
+    }
+}
+
+/// Excel's default 56-color palette as `0xRRGGBB` values, starting with `[Color1]`
+pub const DEFAULT_PALETTE: [u32; 56] = [
+    0x000000, 0xFFFFFF, 0xFF0000, 0x00FF00, 0x0000FF, 0xFFFF00, 0xFF00FF, 0x00FFFF, // 1-8
+    0x800000, 0x008000, 0x000080, 0x808000, 0x800080, 0x008080, 0xC0C0C0, 0x808080, // 9-16
+    0x9999FF, 0x993366, 0xFFFFCC, 0xCCFFFF, 0x660066, 0xFF8080, 0x0066CC, 0xCCCCFF, // 17-24
+    0x000080, 0xFF00FF, 0xFFFF00, 0x00FFFF, 0x800080, 0x800000, 0x008080, 0x0000FF, // 25-32
+    0x00CCFF, 0xCCFFFF, 0xCCFFCC, 0xFFFF99, 0x99CCFF, 0xFF99CC, 0xCC99FF, 0xFFCC99, // 33-40
+    0x3366FF, 0x33CCCC, 0x99CC00, 0xFFCC00, 0xFF9900, 0xFF6600, 0x666699, 0x969696, // 41-48
+    0x003366, 0x339966, 0x003300, 0x333300, 0x993300, 0x993366, 0x333399, 0x333333, // 49-56
+];
+
+/// Palette used to resolve section colors to RGB values
+///
+/// Workbooks can redefine palette entries; named colors follow the palette too,
+/// so changing entry 3 also changes what `[Red]` looks like.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColorPalette {
+    /// Palette entries as `0xRRGGBB`, starting with `[Color1]`
+    pub colors: [u32; 56],
+}
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        ColorPalette {
+            colors: DEFAULT_PALETTE,
+        }
+    }
+}
+
+impl ColorPalette {
+    /// Sets the RGB value (`0xRRGGBB`) of the 1-based palette entry; out-of-range indices are ignored.
+    pub fn with_color(mut self, index: u8, rgb: u32) -> Self {
+        if let Some(entry) = (index as usize)
+            .checked_sub(1)
+            .and_then(|i| self.colors.get_mut(i))
+        {
+            *entry = rgb;
+        }
+        self
+    }
+
+    /// Resolves a color to its RGB value (`0xRRGGBB`), or `None` for an index outside the palette
+    pub fn rgb(&self, color: &ColorType) -> Option<u32> {
+        (color.palette_index() as usize)
+            .checked_sub(1)
+            .and_then(|i| self.colors.get(i))
+            .copied()
+    }
 }
 
 impl FormatToken {
@@ -144,6 +282,17 @@ impl FormatToken {
                 | FormatToken::DayNumPadded
                 | FormatToken::WeekdayAbbr
                 | FormatToken::WeekdayFullName
+                | FormatToken::EraNameShort
+                | FormatToken::EraNameAbbr
+                | FormatToken::EraNameFull
+                | FormatToken::EraYear
+                | FormatToken::EraYearPadded
+                | FormatToken::Quarter
+                | FormatToken::QuarterName
+                | FormatToken::IsoWeek
+                | FormatToken::IsoWeekYear
+                | FormatToken::DayOfYear
+                | FormatToken::DayOfYearPadded
                 | FormatToken::Hour12Or24
                 | FormatToken::Hour12Or24Padded
                 | FormatToken::MinuteNum
@@ -180,6 +329,17 @@ impl FormatToken {
                 | FormatToken::DayNumPadded
                 | FormatToken::WeekdayAbbr
                 | FormatToken::WeekdayFullName
+                | FormatToken::EraNameShort
+                | FormatToken::EraNameAbbr
+                | FormatToken::EraNameFull
+                | FormatToken::EraYear
+                | FormatToken::EraYearPadded
+                | FormatToken::Quarter
+                | FormatToken::QuarterName
+                | FormatToken::IsoWeek
+                | FormatToken::IsoWeekYear
+                | FormatToken::DayOfYear
+                | FormatToken::DayOfYearPadded
                 | FormatToken::Hour12Or24
                 | FormatToken::Hour12Or24Padded
                 | FormatToken::MinuteNum
@@ -232,6 +392,20 @@ pub struct Condition {
     pub value: f64,
 }
 
+impl Condition {
+    /// Checks if the value satisfies the condition
+    pub fn matches(&self, value: f64) -> bool {
+        match self.operator {
+            ComparisonOperator::Eq => value == self.value,
+            ComparisonOperator::Gt => value > self.value,
+            ComparisonOperator::Lt => value < self.value,
+            ComparisonOperator::Ge => value >= self.value,
+            ComparisonOperator::Le => value <= self.value,
+            ComparisonOperator::Ne => value != self.value,
+        }
+    }
+}
+
 /// Represents a section of the format string with optional condition and a sequence of tokens
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct FormatSection {
@@ -266,6 +440,417 @@ pub struct NumberFormat {
     pub text_section: Option<FormatSection>,
 }
 
+/// The section of a format that was used to display a value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SelectedSection {
+    /// The first section, used for positive values and as the fallback
+    Positive,
+    /// The second section, used for negative values
+    Negative,
+    /// The third section, used for zero
+    Zero,
+    /// The fourth section, used for text
+    Text,
+    /// A section chosen because its condition matched; holds the section index (0-2)
+    Conditional(usize),
+}
+
+/// How the selected section interpreted the value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Plain number, including General, percent and scientific formats
+    Number,
+    /// Point in time, such as `yyyy-mm-dd`
+    Date,
+    /// Elapsed time, such as `[h]:mm`
+    Duration,
+    /// Fraction, such as `# ?/?`
+    Fraction,
+    /// Text, formatted with `@`
+    Text,
+}
+
+/// Result of formatting a value, with the information needed to render it
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormattedValue {
+    /// The formatted text
+    pub text: String,
+    /// Color of the selected section, if any
+    pub color: Option<ColorType>,
+    /// Which section was used
+    pub section: SelectedSection,
+    /// How the value was interpreted
+    pub value_kind: ValueKind,
+}
+
+/// Epoch used to turn serial numbers into dates
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DateSystem {
+    /// Serial 1 is 1900-01-01, with Excel's phantom 1900-02-29 at serial 60
+    #[default]
+    Excel1900,
+    /// Serial 0 is 1904-01-01, used by workbooks from older Mac versions of Excel
+    Excel1904,
+}
+
+/// How digits beyond those a format shows are rounded
+///
+/// Values are rounded as magnitudes, so `HalfUp` takes -2.5 to -3 shown as
+/// `-3`; only `Floor` and `Ceiling` look at the sign.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RoundingMode {
+    /// Ties away from zero, as Excel rounds: 2.5 → 3, -2.5 → -3
+    #[default]
+    HalfUp,
+    /// Ties to the even neighbour, also known as banker's rounding: 2.5 → 2, 3.5 → 4
+    HalfEven,
+    /// Ties toward zero: 2.5 → 2, -2.5 → -2
+    HalfDown,
+    /// Drop the extra digits: 2.9 → 2, -2.9 → -2
+    TowardZero,
+    /// Round any remainder up in magnitude: 2.1 → 3, -2.1 → -3
+    AwayFromZero,
+    /// Toward negative infinity: 2.9 → 2, -2.1 → -3
+    Floor,
+    /// Toward positive infinity: 2.1 → 3, -2.9 → -2
+    Ceiling,
+}
+
+/// Options that change how format codes are parsed
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ParseOptions {
+    /// Accept tokens beyond Excel's: `Q` and `QQ` for the quarter, `WW` for
+    /// the ISO week, `IYYY` for the ISO week-based year and `j`/`jjj` for the
+    /// day of the year. Off by default, so Excel codes parse unchanged.
+    pub extended_tokens: bool,
+}
+
+impl ParseOptions {
+    /// Sets whether the extended date tokens are accepted.
+    pub fn with_extended_tokens(mut self, extended_tokens: bool) -> Self {
+        self.extended_tokens = extended_tokens;
+        self
+    }
+}
+
+/// Options that change how values are formatted, independent of the locale
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FormatOptions {
+    /// Date system used for date and time sections
+    pub date_system: DateSystem,
+    /// Show negative serials as dates before the epoch, as LibreOffice does,
+    /// instead of filling the output with `#` like Excel
+    pub proleptic_dates: bool,
+    /// Show negative elapsed times with a minus sign, as LibreOffice does,
+    /// instead of filling the output with `#` like Excel
+    pub signed_durations: bool,
+    /// Round numbers to 15 significant digits before showing them, as Excel
+    /// does, so that 0.1 + 0.2 shows as 0.3 rather than 0.30000000000000004
+    pub excel_precision: bool,
+    /// How numbers, fractions and fractional seconds are rounded to the digits shown
+    pub rounding_mode: RoundingMode,
+}
+
+impl FormatOptions {
+    /// Sets the date system.
+    pub fn with_date_system(mut self, date_system: DateSystem) -> Self {
+        self.date_system = date_system;
+        self
+    }
+
+    /// Sets whether negative serials are shown as dates before the epoch.
+    pub fn with_proleptic_dates(mut self, proleptic_dates: bool) -> Self {
+        self.proleptic_dates = proleptic_dates;
+        self
+    }
+
+    /// Sets whether negative elapsed times are shown with a minus sign.
+    pub fn with_signed_durations(mut self, signed_durations: bool) -> Self {
+        self.signed_durations = signed_durations;
+        self
+    }
+
+    /// Sets whether numbers are rounded to 15 significant digits before formatting.
+    pub fn with_excel_precision(mut self, excel_precision: bool) -> Self {
+        self.excel_precision = excel_precision;
+        self
+    }
+
+    /// Sets the rounding mode.
+    pub fn with_rounding_mode(mut self, rounding_mode: RoundingMode) -> Self {
+        self.rounding_mode = rounding_mode;
+        self
+    }
+}
+
+/// A spreadsheet cell value
+#[derive(Debug, Clone, PartialEq)]
+pub enum CellValue {
+    /// A number, including dates and times stored as serial numbers
+    Number(f64),
+    /// A text value
+    Text(String),
+    /// A boolean value
+    Bool(bool),
+    /// An error value such as `#N/A` or `#DIV/0!`, shown as is
+    Error(String),
+    /// An empty cell
+    Empty,
+}
+
+/// A chrono date, time or duration to format without going through a serial number
+///
+/// Serial numbers are `f64` days, which cannot hold nanoseconds for dates far
+/// from the epoch; formatting these values directly keeps every digit.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TemporalValue {
+    /// A calendar date at midnight
+    Date(chrono::NaiveDate),
+    /// A date and time of day
+    DateTime(chrono::NaiveDateTime),
+    /// A time of day, shown on Excel's day 0 by date tokens
+    Time(chrono::NaiveTime),
+    /// Elapsed time, for `[h]`, `[mm]` and `[ss]` sections
+    Duration(chrono::Duration),
+}
+
+impl TemporalValue {
+    /// Excel serial number of the value in the given date system
+    ///
+    /// Returns `None` for dates Excel cannot store: before serial 0 or after 9999-12-31.
+    pub fn to_excel_serial(&self, date_system: DateSystem) -> Option<f64> {
+        crate::formatter::datetime::temporal_to_excel_serial(self, date_system)
+    }
+}
+
+impl From<chrono::NaiveDate> for TemporalValue {
+    fn from(value: chrono::NaiveDate) -> Self {
+        TemporalValue::Date(value)
+    }
+}
+
+impl From<chrono::NaiveDateTime> for TemporalValue {
+    fn from(value: chrono::NaiveDateTime) -> Self {
+        TemporalValue::DateTime(value)
+    }
+}
+
+impl From<chrono::NaiveTime> for TemporalValue {
+    fn from(value: chrono::NaiveTime) -> Self {
+        TemporalValue::Time(value)
+    }
+}
+
+impl From<chrono::Duration> for TemporalValue {
+    fn from(value: chrono::Duration) -> Self {
+        TemporalValue::Duration(value)
+    }
+}
+
+/// An exact decimal number, such as a money amount stored as text
+///
+/// The value is held as decimal digits and a power of ten, so amounts like
+/// 1.005 or 12345678901234567890.125 keep every digit that an `f64` would lose.
+/// Parse one from a string, or build it from a mantissa and a scale.
+///
+/// # Examples
+/// ```
+/// use number_format::types::Decimal;
+///
+/// let amount: Decimal = "-1234.50".parse().unwrap();
+/// assert_eq!(amount, Decimal::new(-12345, 1));
+/// assert_eq!(amount.to_f64(), -1234.5);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Decimal {
+    negative: bool,
+    /// Significant digits, most significant first, without leading or trailing zeros
+    digits: Vec<u8>,
+    /// Power of ten of the last digit
+    exponent: i32,
+}
+
+impl Decimal {
+    /// The number `mantissa / 10^scale`, e.g. `Decimal::new(12345, 2)` is 123.45
+    pub fn new(mantissa: i128, scale: u32) -> Self {
+        Decimal::from_parts(
+            mantissa < 0,
+            integer_digits(mantissa.unsigned_abs()),
+            -i64::from(scale),
+        )
+    }
+
+    /// Whether the number is below zero
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// Whether the number is zero
+    pub fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    /// The nearest `f64`, used for conditions and sections that are not exact
+    pub fn to_f64(&self) -> f64 {
+        if self.is_zero() {
+            return 0.0;
+        }
+        let digits: String = self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+        let sign = if self.negative { "-" } else { "" };
+        format!("{}{}e{}", sign, digits, self.exponent)
+            .parse()
+            .unwrap_or(0.0)
+    }
+
+    /// The same number without its sign
+    pub fn abs(&self) -> Self {
+        Decimal {
+            negative: false,
+            ..self.clone()
+        }
+    }
+
+    /// The number multiplied by `10^power`
+    pub fn shift(&self, power: i32) -> Self {
+        Decimal::from_parts(
+            self.negative,
+            self.digits.clone(),
+            i64::from(self.exponent) + i64::from(power),
+        )
+    }
+
+    /// The shortest decimal that reads back as the same `f64`, if it is finite
+    pub(crate) fn from_f64(value: f64) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        format!("{:e}", value).parse().ok()
+    }
+
+    /// The decimal an `f64` shows when rounded to `significant` digits, if it is finite
+    pub(crate) fn from_f64_significant(value: f64, significant: usize) -> Option<Self> {
+        if !value.is_finite() {
+            return None;
+        }
+        format!("{:.*e}", significant.saturating_sub(1), value)
+            .parse()
+            .ok()
+    }
+
+    /// Significant digits, most significant first; empty for zero
+    pub(crate) fn digits(&self) -> &[u8] {
+        &self.digits
+    }
+
+    /// Power of ten of the last significant digit
+    pub(crate) fn exponent(&self) -> i32 {
+        self.exponent
+    }
+
+    /// Build a normalized decimal from `digits × 10^exponent`
+    fn from_parts(negative: bool, mut digits: Vec<u8>, mut exponent: i64) -> Self {
+        let leading = digits.iter().take_while(|d| **d == 0).count();
+        digits.drain(..leading);
+        while digits.last() == Some(&0) {
+            digits.pop();
+            exponent += 1;
+        }
+        if digits.is_empty() {
+            exponent = 0;
+        }
+        Decimal {
+            negative: negative && !digits.is_empty(),
+            digits,
+            exponent: exponent.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32,
+        }
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Self {
+        Decimal::new(i128::from(value), 0)
+    }
+}
+
+impl From<u64> for Decimal {
+    fn from(value: u64) -> Self {
+        Decimal::new(i128::from(value), 0)
+    }
+}
+
+impl From<i128> for Decimal {
+    fn from(value: i128) -> Self {
+        Decimal::new(value, 0)
+    }
+}
+
+impl From<u128> for Decimal {
+    fn from(value: u128) -> Self {
+        Decimal::from_parts(false, integer_digits(value), 0)
+    }
+}
+
+/// Largest power of ten, up or down, of the first digit of a parsed `Decimal`
+///
+/// Formatting lays out every digit down to the units, so a number like
+/// `1e2000000000` would take gigabytes to show in a `0` section.
+const MAX_PARSED_EXPONENT: i64 = 10_000;
+
+/// Decimal digits of an integer, most significant first
+fn integer_digits(value: u128) -> Vec<u8> {
+    value.to_string().bytes().map(|b| b - b'0').collect()
+}
+
+impl std::str::FromStr for Decimal {
+    type Err = ParseDecimalError;
+
+    /// Parse a number like `-12345.678`, `.5` or `1.5e-3`
+    ///
+    /// Numbers of 10^10000 and more, or non-zero numbers below 10^-10000, are rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (number, power) = match rest.split_once(['e', 'E']) {
+            Some((number, power)) => (number, power.parse::<i32>().map_err(|_| ParseDecimalError)?),
+            None => (rest, 0),
+        };
+        let (whole, fraction) = number.split_once('.').unwrap_or((number, ""));
+        if whole.is_empty() && fraction.is_empty()
+            || !whole
+                .bytes()
+                .chain(fraction.bytes())
+                .all(|b| b.is_ascii_digit())
+        {
+            return Err(ParseDecimalError);
+        }
+        let digits = whole
+            .bytes()
+            .chain(fraction.bytes())
+            .map(|b| b - b'0')
+            .collect();
+        let value = Decimal::from_parts(negative, digits, i64::from(power) - fraction.len() as i64);
+        let leading_power = i64::from(value.exponent) + value.digits.len() as i64 - 1;
+        if !value.is_zero() && leading_power.abs() > MAX_PARSED_EXPONENT {
+            return Err(ParseDecimalError);
+        }
+        Ok(value)
+    }
+}
+
+/// Error returned when a string is not a decimal number
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseDecimalError;
+
+impl std::fmt::Display for ParseDecimalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid decimal number")
+    }
+}
+
+impl std::error::Error for ParseDecimalError {}
+
 /// Locale-specific settings for number formatting.
 #[derive(Debug, Clone, PartialEq)]
 pub struct LocaleSettings {
@@ -297,6 +882,18 @@ pub struct LocaleSettings {
     /// Currency symbol for locale-dependent formatting
     pub currency_symbol: String,
 
+    /// Names of the boolean values, e.g., `["TRUE", "FALSE"]` or `["WAHR", "FALSCH"]`.
+    /// Should contain two elements: [true_name, false_name].
+    pub boolean_names: [String; 2],
+
+    /// Month names of the Hijri calendar, Muharram to Dhu al-Hijjah.
+    /// Should contain 12 elements, starting with Muharram.
+    pub hijri_month_names: [String; 12],
+
+    /// Quarter names for the extended `QQ` token, e.g., `["1st quarter", ..., "4th quarter"]`.
+    /// Should contain 4 elements.
+    pub quarter_names: [String; 4],
+
     /// Full month names, January to December, e.g., `["January", "February", ..., "December"]`.
     pub month_names_full: Vec<String>,
 
@@ -311,6 +908,27 @@ impl Default for LocaleSettings {
             thousands_separator: ',',
             currency_symbol: "$".to_string(),
             ampm_markers: ["AM".to_string(), "PM".to_string()],
+            boolean_names: ["TRUE".to_string(), "FALSE".to_string()],
+            hijri_month_names: [
+                "Muharram".to_string(),
+                "Safar".to_string(),
+                "Rabi' al-Awwal".to_string(),
+                "Rabi' al-Thani".to_string(),
+                "Jumada al-Ula".to_string(),
+                "Jumada al-Akhirah".to_string(),
+                "Rajab".to_string(),
+                "Sha'ban".to_string(),
+                "Ramadan".to_string(),
+                "Shawwal".to_string(),
+                "Dhu al-Qi'dah".to_string(),
+                "Dhu al-Hijjah".to_string(),
+            ],
+            quarter_names: [
+                "1st quarter".to_string(),
+                "2nd quarter".to_string(),
+                "3rd quarter".to_string(),
+                "4th quarter".to_string(),
+            ],
             short_day_names: [
                 "Sun".to_string(),
                 "Mon".to_string(),
@@ -409,6 +1027,13 @@ impl LocaleSettings {
         self
     }
 
+    /// Sets the names of the boolean values.
+    /// Expects an array of two string slices: `[true_name, false_name]`.
+    pub fn with_boolean_names(mut self, names: [&str; 2]) -> Self {
+        self.boolean_names = [names[0].to_string(), names[1].to_string()];
+        self
+    }
+
     /// Sets the short day names (Sunday to Saturday).
     /// Expects an array of seven string slices.
     pub fn with_short_day_names(mut self, names: [&str; 7]) -> Self {
@@ -437,6 +1062,20 @@ impl LocaleSettings {
         self
     }
 
+    /// Sets the Hijri month names (Muharram to Dhu al-Hijjah).
+    /// Expects an array of twelve string slices.
+    pub fn with_hijri_month_names(mut self, names: [&str; 12]) -> Self {
+        self.hijri_month_names = names.map(|s| s.to_string());
+        self
+    }
+
+    /// Sets the quarter names shown by the extended `QQ` token.
+    /// Expects an array of four string slices.
+    pub fn with_quarter_names(mut self, names: [&str; 4]) -> Self {
+        self.quarter_names = names.map(|s| s.to_string());
+        self
+    }
+
     /// Sets the currency symbol.
     pub fn with_currency_symbol(mut self, symbol: String) -> Self {
         self.currency_symbol = symbol;
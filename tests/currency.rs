@@ -5,7 +5,6 @@ mod tests {
         parse_number_format,                   // The public one from lib.rs
         types::{LocaleSettings, NumberFormat}, // LocaleSettings and NumberFormat from types module
     };
-    // NumberFormatError is not a type, parse_number_format returns Result<_, String>
 
     // Helper function to format with a specific locale and format string
     fn fmt_currency(
@@ -13,7 +12,7 @@ mod tests {
         format_code: &str,
         locale: &LocaleSettings,
     ) -> Result<String, String> {
-        let fmt: NumberFormat = parse_number_format(format_code)?;
+        let fmt: NumberFormat = parse_number_format(format_code).map_err(|e| e.to_string())?;
         Ok(format_number(value, &fmt, locale))
     }
 
@@ -44,6 +43,16 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_bracketed_currency_symbols() -> Result<(), String> {
+        let locale = LocaleSettings::default();
+        assert_eq!(fmt_currency(1234.5, "[$€]#,##0", &locale)?, "€1,235");
+        assert_eq!(fmt_currency(1234.5, "[$USD] #,##0", &locale)?, "USD 1,235");
+        assert_eq!(fmt_currency(-1234.5, "[$€]#,##0;-0", &locale)?, "-1235");
+        assert_eq!(fmt_currency(1234.5, "[$€-407]#,##0", &locale)?, "€1,235");
+        Ok(())
+    }
+
     #[test]
     fn test_locale_currency_symbol_with_text() -> Result<(), String> {
         let custom_locale = LocaleSettings::default()
@@ -1,6 +1,9 @@
-use number_format::formatter::format_number;
-use number_format::parser::parse_number_format;
-use number_format::types::LocaleSettings;
+use chrono::{Duration, NaiveDate, NaiveTime};
+use number_format::formatter::{format_number, format_number_with_options, format_temporal};
+use number_format::parser::{parse_number_format, parse_number_format_with_options};
+use number_format::types::{
+    DateSystem, FormatOptions, LocaleSettings, ParseOptions, TemporalValue,
+};
 
 // Helper to create default locale settings
 fn default_loc() -> LocaleSettings {
@@ -84,7 +87,33 @@ fn test_month_formats() {
     assert_eq!(
         format_number(val, &parse_number_format("mmmmm").unwrap(), &default_loc()),
         "J"
-    ); // Fixed English letter
+    );
+    // The first letter of the month name in the section's locale
+    assert_eq!(
+        format_number(
+            val,
+            &parse_number_format("[$-41E]mmmmm").unwrap(),
+            &default_loc()
+        ),
+        "ม"
+    );
+    assert_eq!(
+        format_number(
+            val,
+            &parse_number_format("[$-804]mmmmm").unwrap(),
+            &default_loc()
+        ),
+        "一"
+    );
+    // Capitalized even where the month names are lowercase
+    assert_eq!(
+        format_number(
+            44562.189571759256,
+            &parse_number_format("[$-1C0C]mmmmm dd yyyy  h:mm AM/PM").unwrap(),
+            &default_loc()
+        ),
+        "J 01 2022  4:32 AM"
+    );
 }
 
 // --- Day/Weekday Representations ---
@@ -184,12 +213,39 @@ fn test_duration_h_mm_ss() {
 }
 
 #[test]
-fn test_duration_negative_input_error() {
-    let fmt = parse_number_format("[h]:mm").unwrap();
+fn test_duration_negative_values() {
+    let signed = FormatOptions::default().with_signed_durations(true);
+    let format = |code: &str, value: f64, options: &FormatOptions| {
+        format_number_with_options(
+            value,
+            &parse_number_format(code).unwrap(),
+            &default_loc(),
+            options,
+        )
+    };
+
+    // Excel fills the cell with # for negative elapsed times
+    let excel = FormatOptions::default();
+    assert_eq!(format("[h]:mm", -1.0, &excel), "######");
+    assert_eq!(format("[h]:mm;-[h]:mm", -1.0, &excel), "#######");
+
+    assert_eq!(format("[h]:mm", -1.0 / 16.0, &signed), "-1:30");
+    assert_eq!(format("[h]:mm", -1.0, &signed), "-24:00");
+    assert_eq!(format("[mm]:ss", -90.0 / 86400.0, &signed), "-01:30");
+    // A negative section supplies its own sign
+    assert_eq!(format("[h]:mm;([h]:mm)", -1.0 / 16.0, &signed), "(1:30)");
+    // The magnitude is rounded, carrying into minutes like a positive duration
+    assert_eq!(
+        format("[h]:mm:ss.00", -59.996 / 86400.0, &signed),
+        "-0:01:00.00"
+    );
     assert_eq!(
-        format_number(-1.0, &fmt, &default_loc()),
-        "ERROR: Negative value (-1) not allowed for duration format."
+        format("[h]:mm:ss.000", -1.2345 / 86400.0, &signed),
+        "-0:00:01.235"
     );
+    // Values that round to zero have no sign
+    assert_eq!(format("[h]:mm:ss", -0.4 / 86400.0, &signed), "0:00:00");
+    assert_eq!(format("[h]:mm:ss", -0.4 / 86400.0, &excel), "0:00:00");
 }
 
 // --- Literal and Quoted Text ---
@@ -244,3 +300,305 @@ fn test_excel_1900_bug_date() {
     // Serial 61 is 1900-03-01
     assert_eq!(format_number(61.0, &fmt_d, &default_loc()), "1900-03-01");
 }
+
+// --- Date System Tests ---
+#[test]
+fn test_1904_date_system() {
+    let options_1904 = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+    let fmt = parse_number_format("yyyy-mm-dd hh:mm").unwrap();
+
+    assert_eq!(
+        format_number_with_options(0.0, &fmt, &default_loc(), &options_1904),
+        "1904-01-01 00:00"
+    );
+    // The same day is 1462 days later in the 1900 system
+    assert_eq!(
+        format_number_with_options(1462.5, &fmt, &default_loc(), &FormatOptions::default()),
+        "1904-01-01 12:00"
+    );
+    // Serial 60 is a real day, not the phantom 1900-02-29
+    assert_eq!(
+        format_number_with_options(60.0, &fmt, &default_loc(), &options_1904),
+        "1904-03-01 00:00"
+    );
+    assert_eq!(
+        format_number_with_options(
+            TEST_DATE_SERIAL - 1462.0,
+            &fmt,
+            &default_loc(),
+            &options_1904
+        ),
+        "2024-01-01 18:00"
+    );
+
+    // Elapsed time does not depend on the epoch
+    let duration_fmt = parse_number_format("[h]:mm:ss").unwrap();
+    assert_eq!(
+        format_number_with_options(1.5, &duration_fmt, &default_loc(), &options_1904),
+        "36:00:00"
+    );
+}
+
+// --- Out-of-range Serial Tests ---
+#[test]
+fn test_out_of_range_serials_render_as_hashes() {
+    let fmt = parse_number_format("yyyy-mm-dd").unwrap();
+    assert_eq!(format_number(-1.0, &fmt, &default_loc()), "##########");
+    assert_eq!(format_number(2958466.0, &fmt, &default_loc()), "##########");
+    assert_eq!(format_number(2958465.5, &fmt, &default_loc()), "9999-12-31");
+
+    let fmt = parse_number_format("d mmmm").unwrap();
+    assert_eq!(format_number(-0.5, &fmt, &default_loc()), "#########");
+}
+
+#[test]
+fn test_proleptic_negative_serials() {
+    let options = FormatOptions::default().with_proleptic_dates(true);
+    let fmt = parse_number_format("yyyy-mm-dd hh:mm").unwrap();
+
+    assert_eq!(
+        format_number_with_options(-1.0, &fmt, &default_loc(), &options),
+        "1899-12-29 00:00"
+    );
+    assert_eq!(
+        format_number_with_options(-0.25, &fmt, &default_loc(), &options),
+        "1899-12-29 18:00"
+    );
+    assert_eq!(
+        format_number_with_options(-36522.0, &fmt, &default_loc(), &options),
+        "1800-01-01 00:00"
+    );
+    // Non-negative serials are unaffected
+    assert_eq!(
+        format_number_with_options(TEST_DATE_SERIAL, &fmt, &default_loc(), &options),
+        "2024-01-01 18:00"
+    );
+
+    let options_1904 = options.with_date_system(DateSystem::Excel1904);
+    assert_eq!(
+        format_number_with_options(-1.0, &fmt, &default_loc(), &options_1904),
+        "1903-12-31 00:00"
+    );
+}
+
+#[test]
+fn test_format_chrono_values() {
+    let options = FormatOptions::default();
+    let format = |value: TemporalValue, code: &str| {
+        let fmt = parse_number_format(code).unwrap();
+        format_temporal(&value, &fmt, &default_loc(), &options)
+    };
+
+    let dt = NaiveDate::from_ymd_opt(2024, 5, 6)
+        .unwrap()
+        .and_hms_nano_opt(7, 8, 9, 123_456_789)
+        .unwrap();
+    // All nine digits survive, unlike with the serial 45418.29733...
+    assert_eq!(
+        format(dt.into(), "hh:mm:ss.000000000"),
+        "07:08:09.123456789"
+    );
+    assert_eq!(format(dt.into(), "yyyy-mm-dd hh:mm"), "2024-05-06 07:08");
+    assert_eq!(format(dt.date().into(), "d mmm yyyy"), "6 May 2024");
+    // Non-date sections see the serial number
+    assert_eq!(format(dt.date().into(), "0.00"), "45418.00");
+
+    let time = NaiveTime::from_hms_opt(13, 30, 0).unwrap();
+    assert_eq!(format(time.into(), "h:mm AM/PM"), "1:30 PM");
+
+    let duration = Duration::seconds(1_000_000) + Duration::nanoseconds(123_456_789);
+    assert_eq!(format(duration.into(), "[h]:mm:ss.000"), "277:46:40.123");
+    assert_eq!(format(duration.into(), "[mm]:ss"), "16666:40");
+
+    // Dates Excel cannot store
+    let old = NaiveDate::from_ymd_opt(1800, 1, 1).unwrap();
+    assert_eq!(format(old.into(), "yyyy-mm-dd"), "##########");
+}
+
+#[test]
+fn test_chrono_to_excel_serial() {
+    let serial = |y, m, d, date_system| {
+        TemporalValue::from(NaiveDate::from_ymd_opt(y, m, d).unwrap()).to_excel_serial(date_system)
+    };
+
+    assert_eq!(serial(1899, 12, 31, DateSystem::Excel1900), Some(0.0));
+    assert_eq!(serial(1900, 1, 1, DateSystem::Excel1900), Some(1.0));
+    assert_eq!(serial(1900, 2, 28, DateSystem::Excel1900), Some(59.0));
+    // Serial 60 is the non-existent 1900-02-29
+    assert_eq!(serial(1900, 3, 1, DateSystem::Excel1900), Some(61.0));
+    assert_eq!(serial(2024, 1, 1, DateSystem::Excel1900), Some(45292.0));
+    assert_eq!(serial(2024, 1, 1, DateSystem::Excel1904), Some(43830.0));
+    assert_eq!(serial(1899, 12, 30, DateSystem::Excel1900), None);
+    assert_eq!(serial(1903, 12, 31, DateSystem::Excel1904), None);
+
+    let noon = NaiveTime::from_hms_opt(12, 0, 0).unwrap();
+    assert_eq!(
+        TemporalValue::from(noon).to_excel_serial(DateSystem::Excel1900),
+        Some(0.5)
+    );
+    assert_eq!(
+        TemporalValue::from(Duration::hours(36)).to_excel_serial(DateSystem::Excel1900),
+        Some(1.5)
+    );
+}
+
+#[test]
+fn test_non_gregorian_calendars() {
+    let format = |code: &str, value: f64, locale: &LocaleSettings| {
+        format_number(value, &parse_number_format(code).unwrap(), locale)
+    };
+    let arabic = number_format::get_locale_settings("ar").unwrap();
+
+    // 2024-01-01 is 19 Jumada al-Akhirah 1445 in the tabular Hijri calendar
+    assert_eq!(
+        format("B2dd/mm/yyyy", 45292.0, &default_loc()),
+        "19/06/1445"
+    );
+    assert_eq!(
+        format("[$-1060000]d mmmm yyyy", 45292.0, &default_loc()),
+        "19 Jumada al-Akhirah 1445"
+    );
+    assert_eq!(
+        format("B2d mmmm yyyy", 45292.0, &arabic),
+        "19 جمادى الآخرة 1445"
+    );
+    // 2024-07-07 starts the year 1446
+    assert_eq!(
+        format("B2yyyy-mm-dd", 45480.0, &default_loc()),
+        "1445-12-30"
+    );
+    assert_eq!(
+        format("B2yyyy-mm-dd", 45481.0, &default_loc()),
+        "1446-01-01"
+    );
+
+    // Thai Buddhist years are 543 years ahead, months and days are unchanged
+    assert_eq!(
+        format("[$-107041E]d/mm/yyyy", 45292.0, &default_loc()),
+        "1/01/2567"
+    );
+    // The LCID part (041E) selects Thai month names
+    assert_eq!(
+        format("[$-107041E]d mmm yy", 45292.0, &default_loc()),
+        "1 ม.ค. 67"
+    );
+    assert_eq!(
+        format("[$-70000]d mmm yy", 45292.0, &default_loc()),
+        "1 Jan 67"
+    );
+    assert_eq!(
+        format("[$฿-107041E]d/mm/yyyy", 45292.0, &default_loc()),
+        "฿1/01/2567"
+    );
+
+    // B1 is the Gregorian calendar
+    assert_eq!(
+        format("B1dd/mm/yyyy", 45292.0, &default_loc()),
+        "01/01/2024"
+    );
+}
+
+#[test]
+fn test_japanese_era() {
+    let japanese = number_format::get_locale_settings("ja").unwrap();
+    let format = |code: &str, value: f64| {
+        format_number(value, &parse_number_format(code).unwrap(), &japanese)
+    };
+
+    // 2024-05-06
+    assert_eq!(format("ggge\"年\"m\"月\"d\"日\"", 45418.0), "令和6年5月6日");
+    assert_eq!(format("[$-411]gge\"年\"", 45418.0), "令6年");
+    // Reiwa began on 2019-05-01, the day after Heisei 31 ended
+    assert_eq!(format("[$-411]ggge\"年\"", 43585.0), "平成31年");
+    assert_eq!(format("[$-411]ggge\"年\"", 43586.0), "令和1年");
+    // 1989-01-08, the first day of Heisei
+    assert_eq!(format("gee.mm.dd", 32516.0), "H01.01.08");
+    assert_eq!(format("gee.mm.dd", 32515.0), "S64.01.07");
+
+    // Other locales have no eras: `g` shows nothing and `e` the year
+    let format = |code: &str, value: f64| {
+        format_number(value, &parse_number_format(code).unwrap(), &default_loc())
+    };
+    assert_eq!(format("e", 45000.0), "2023");
+    assert_eq!(format("ggge.mm.dd", 45000.0), "2023.03.15");
+    assert_eq!(format("[$-404]ggg", 45000.0), "");
+    assert_eq!(format("[$-411]ggge", 45000.0), "令和5");
+    assert_eq!(format("[$-ja-JP]ge", 45000.0), "R5");
+}
+
+#[test]
+fn test_dbnum_dates() {
+    let format = |code: &str, value: f64| {
+        format_number(value, &parse_number_format(code).unwrap(), &default_loc())
+    };
+
+    // Years are read digit by digit, months and days with units
+    assert_eq!(
+        format("[DBNum1]yyyy\"年\"m\"月\"d\"日\"", 45651.0),
+        "二○二四年十二月二十五日"
+    );
+    assert_eq!(format("[DBNum2]h:mm", 0.5), "壹拾贰:零零");
+    assert_eq!(format("[DBNum3]yyyy/mm/dd", 45418.0), "２０２４/０５/０６");
+    assert_eq!(format("[DBNum1][h]:mm", 1.5), "三十六:○○");
+}
+
+#[test]
+fn test_locale_tag_selects_names() {
+    let format = |code: &str, value: f64, locale: &LocaleSettings| {
+        format_number(value, &parse_number_format(code).unwrap(), locale)
+    };
+
+    // 2024-05-06 14:00, a Monday
+    let value = 45418.0 + 14.0 / 24.0;
+    assert_eq!(
+        format("[$-407]dddd, d. mmmm", value, &default_loc()),
+        "Montag, 6. Mai"
+    );
+    assert_eq!(
+        format("[$-409]mmmm h AM/PM", value, &french_loc()),
+        "May 2 PM"
+    );
+    assert_eq!(format("[$-804]h AM/PM", value, &default_loc()), "2 下午");
+    // Only the tagged section is affected
+    let fmt = parse_number_format("[>=45000]mmmm;[<45000][$-407]mmmm").unwrap();
+    assert_eq!(format_number(value, &fmt, &default_loc()), "May");
+    assert_eq!(format_number(44000.0, &fmt, &default_loc()), "Juni");
+    // Unknown codes keep the given locale
+    assert_eq!(format("[$-FFFF]mmmm", value, &default_loc()), "May");
+    // [$-111] shows the serial number
+    assert_eq!(
+        format("[$-111]MM/DD/YYYY", 43528.0, &default_loc()),
+        "43528"
+    );
+}
+
+#[test]
+fn test_extended_date_tokens() {
+    let options = ParseOptions::default().with_extended_tokens(true);
+    let format = |code: &str, value: f64, locale: &LocaleSettings| {
+        format_number(
+            value,
+            &parse_number_format_with_options(code, &options).unwrap(),
+            locale,
+        )
+    };
+
+    // 2024-09-10 is in the third quarter and ISO week 37
+    let value = 45545.0;
+    assert_eq!(format("Q yyyy", value, &default_loc()), "Q3 2024");
+    assert_eq!(format("QQ", value, &default_loc()), "3rd quarter");
+    assert_eq!(format("\"W\"WW", value, &default_loc()), "W37");
+    assert_eq!(format("j jjj", value, &default_loc()), "254 254");
+    assert_eq!(format("jjj", 45292.0, &default_loc()), "001");
+
+    // 2024-12-30 belongs to week 1 of ISO year 2025
+    assert_eq!(format("IYYY-\"W\"WW", 45656.0, &default_loc()), "2025-W1");
+    // 2021-01-01 belongs to week 53 of ISO year 2020
+    assert_eq!(
+        format("IYYY-\"W\"WW yyyy", 44197.0, &default_loc()),
+        "2020-W53 2021"
+    );
+
+    let german = number_format::get_locale_settings("de").unwrap();
+    assert_eq!(format("QQ yyyy", value, &german), "3. Quartal 2024");
+}
@@ -106,3 +106,29 @@ fn run_all_excelize_toml_cases() {
         );
     }
 }
+
+#[test]
+fn excelize_formats_round_trip() {
+    let toml_content = fs::read_to_string("tests/excelize-numfmt-test.toml")
+        .expect("Failed to read tests/excelize-numfmt-test.toml");
+    let test_data: TestCases = toml::from_str(&toml_content)
+        .expect("Failed to parse TOML from tests/excelize-numfmt-test.toml");
+
+    // Every Excel format code that parses is written back in a form that parses the same
+    let mut checked = 0;
+    for case in &test_data.cases {
+        let Ok(format) = parse_number_format(&case.format) else {
+            continue;
+        };
+        let emitted = format.to_format_code();
+        assert_eq!(
+            parse_number_format(&emitted).as_ref(),
+            Ok(&format),
+            "round trip of {:?} via {:?}",
+            case.format,
+            emitted
+        );
+        checked += 1;
+    }
+    assert!(checked > 3000);
+}
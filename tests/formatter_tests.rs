@@ -1,5 +1,12 @@
-use number_format::types::{FormatToken, LocaleSettings};
-use number_format::{format_number, parse_number_format};
+use number_format::types::{
+    CellValue, ColorType, DateSystem, Decimal, FormatOptions, FormatToken, LocaleSettings,
+    RoundingMode, SelectedSection, ValueKind,
+};
+use number_format::{
+    format_decimal, format_integer, format_number, format_number_rich, format_number_with_options,
+    format_number_with_width, format_number_with_width_and_options, format_text, format_text_rich,
+    format_text_with_width, format_value, format_value_with_options, parse_number_format,
+};
 
 #[test]
 fn test_basic_format() {
@@ -159,6 +166,60 @@ fn test_exponential_format() {
     let locale = LocaleSettings::default();
     assert_eq!(format_number(12345.67, &format, &locale), "1.23E+04");
     assert_eq!(format_number(0.00012345, &format, &locale), "1.23E-04");
+    assert_eq!(format_number(-12345.67, &format, &locale), "-1.23E+04");
+}
+
+#[test]
+fn test_engineering_notation() {
+    let locale = LocaleSettings::default();
+    let format =
+        |code: &str, value: f64| format_number(value, &parse_number_format(code).unwrap(), &locale);
+
+    // Three integer placeholders make exponents multiples of three
+    assert_eq!(format("##0.0E+0", 12345.0), "12.3E+3");
+    assert_eq!(format("##0.0E+0", 123456.0), "123.5E+3");
+    assert_eq!(format("##0.0E+0", 1234.0), "1.2E+3");
+    assert_eq!(format("##0.0E+0", 0.00012345), "123.5E-6");
+    assert_eq!(format("##0.0E+0", -12345.0), "-12.3E+3");
+    assert_eq!(format("##0.0E+0", 0.0), "0.0E+0");
+    // Rounding up to the next multiple moves to the next exponent
+    assert_eq!(format("##0.0E+0", 999960.0), "1.0E+6");
+    assert_eq!(format("#0.0E+0", 12345.0), "1.2E+4");
+    assert_eq!(format("#0.0E+0", 123456.0), "12.3E+4");
+
+    // `0` pads the mantissa with zeros, `#` drops optional digits
+    assert_eq!(format("00.0E+0", 12345.0), "01.2E+4");
+    // Only the number of integer placeholders sets the exponent multiple
+    assert_eq!(format("00.0E+0", 123456.0), "12.3E+4");
+    assert_eq!(format("#0.0E+0", 12345.0), "1.2E+4");
+    assert_eq!(format("?0.0E+0", 12345.0), " 1.2E+4");
+    assert_eq!(format("0#.0E+0", 12345.0), "01.2E+4");
+    assert_eq!(format("000.0E+0", 12345.0), "012.3E+3");
+    assert_eq!(format("#00.0E+0", 12345.0), "12.3E+3");
+    assert_eq!(format("000.0E+0", 1234.0), "001.2E+3");
+    assert_eq!(format("0.0#E+0", 1.2), "1.2E+0");
+    assert_eq!(format("0.0#E+0", 1.25), "1.25E+0");
+    assert_eq!(format(".00E+0", 12345.0), ".12E+5");
+
+    // The exponent is padded to its placeholders
+    assert_eq!(format("0.0E+0", 12345.0), "1.2E+4");
+    assert_eq!(format("0.0E+000", 12345.0), "1.2E+004");
+    assert_eq!(format("0.0E-00", 12345.0), "1.2E04");
+    assert_eq!(format("0.0E-00", 0.00012), "1.2E-04");
+    assert_eq!(format("0.0E+#", 1.0), "1.0E+0");
+
+    // Literals around the mantissa are kept
+    assert_eq!(format("0.00E+00;(0.00E+00)", -12345.67), "(1.23E+04)");
+    assert_eq!(format("0.0E+0\" m\"", 12345.0), "1.2E+4 m");
+
+    assert_eq!(
+        format_decimal(
+            &"123456789012345678901234".parse().unwrap(),
+            &parse_number_format("##0.00E+0").unwrap(),
+            &locale
+        ),
+        "123.46E+21"
+    );
 }
 
 #[test]
@@ -539,3 +600,585 @@ fn test_special_char_formatting() {
     let format_esc_star = parse_number_format("\\*0").unwrap(); // \* -> LiteralChar('*')
     assert_eq!(format_number(2.0, &format_esc_star, &locale), "*2");
 }
+
+#[test]
+fn test_general_with_other_tokens() {
+    let locale = LocaleSettings::default();
+
+    let format = parse_number_format("General\" units\"").unwrap();
+    assert_eq!(format_number(12.5, &format, &locale), "12.5 units");
+    assert_eq!(format_number(-12.5, &format, &locale), "-12.5 units");
+
+    let format = parse_number_format("[Red]General;[Blue]-General").unwrap();
+    assert_eq!(
+        format.negative_section.as_ref().unwrap().color,
+        Some(ColorType::Blue)
+    );
+    assert_eq!(format_number(5.0, &format, &locale), "5");
+    assert_eq!(format_number(-5.0, &format, &locale), "-5");
+    // Without a sign of its own, the negative section keeps the value's sign
+    for code in [";General", "0;General", "0;[Red]General"] {
+        let format = parse_number_format(code).unwrap();
+        assert_eq!(format_number(-123.0, &format, &locale), "-123", "{}", code);
+    }
+    let format = parse_number_format("0;(General)").unwrap();
+    assert_eq!(format_number(-123.0, &format, &locale), "(123)");
+
+    // Quoted "General" is just text
+    let format = parse_number_format("0.00 \"General\"").unwrap();
+    assert_eq!(format_number(1.5, &format, &locale), "1.50 General");
+
+    let format = parse_number_format("[>=100]\"big \"General;General;\"none\"").unwrap();
+    assert_eq!(format_number(150.0, &format, &locale), "big 150");
+    assert_eq!(format_number(0.0, &format, &locale), "none");
+}
+
+#[test]
+fn test_format_number_rich() {
+    let locale = LocaleSettings::default();
+
+    let format = parse_number_format("[Blue]#,##0;[Red]-#,##0;[Green]\"zero\";@").unwrap();
+    let result = format_number_rich(1234.0, &format, &locale);
+    assert_eq!(result.text, "1,234");
+    assert_eq!(result.color, Some(ColorType::Blue));
+    assert_eq!(result.section, SelectedSection::Positive);
+    assert_eq!(result.value_kind, ValueKind::Number);
+
+    let result = format_number_rich(0.0, &format, &locale);
+    assert_eq!(result.text, "zero");
+    assert_eq!(result.color, Some(ColorType::Green));
+    assert_eq!(result.section, SelectedSection::Zero);
+
+    // Text goes through the text section; NaN is not taken for text
+    let result = format_text_rich("abc", &format, &locale);
+    assert_eq!(result.text, "abc");
+    assert_eq!(result.section, SelectedSection::Text);
+    assert_eq!(result.value_kind, ValueKind::Text);
+    assert_eq!(result.color, None);
+    let result = format_number_rich(f64::NAN, &format, &locale);
+    assert_eq!(result.section, SelectedSection::Positive);
+    assert_ne!(result.value_kind, ValueKind::Text);
+
+    let format = parse_number_format("0;-0;0;[Magenta]\"[\"@\"]\"").unwrap();
+    let result = format_text_rich("abc", &format, &locale);
+    assert_eq!(result.text, "[abc]");
+    assert_eq!(result.color, Some(ColorType::Magenta));
+    let format = parse_number_format("[Cyan]\"Name: \"@").unwrap();
+    let result = format_text_rich("abc", &format, &locale);
+    assert_eq!(result.text, "Name: abc");
+    assert_eq!(result.color, Some(ColorType::Cyan));
+    assert_eq!(result.section, SelectedSection::Positive);
+    let format = parse_number_format("[Red]0.00").unwrap();
+    let result = format_text_rich("abc", &format, &locale);
+    assert_eq!(result.text, "abc");
+    assert_eq!(result.color, None);
+
+    let format = parse_number_format("[>=100][Color10]0;[<0]-0;0.0").unwrap();
+    let result = format_number_rich(150.0, &format, &locale);
+    assert_eq!(result.color, Some(ColorType::Indexed(10)));
+    assert_eq!(result.section, SelectedSection::Conditional(0));
+    let result = format_number_rich(-5.0, &format, &locale);
+    assert_eq!(result.section, SelectedSection::Conditional(1));
+
+    let kinds = [
+        ("yyyy-mm-dd", ValueKind::Date),
+        ("[h]:mm", ValueKind::Duration),
+        ("# ?/?", ValueKind::Fraction),
+        ("0.00E+00", ValueKind::Number),
+        ("General", ValueKind::Number),
+    ];
+    for (code, kind) in kinds {
+        let format = parse_number_format(code).unwrap();
+        assert_eq!(
+            format_number_rich(45000.25, &format, &locale).value_kind,
+            kind,
+            "{}",
+            code
+        );
+    }
+}
+
+#[test]
+fn test_format_number_with_width() {
+    let locale = LocaleSettings::default();
+
+    // Fill before and after the number
+    let format = parse_number_format("$* #,##0").unwrap();
+    assert_eq!(
+        format_number_with_width(1234.0, &format, &locale, 10),
+        "$    1,234"
+    );
+    let format = parse_number_format("0*-").unwrap();
+    assert_eq!(
+        format_number_with_width(42.0, &format, &locale, 6),
+        "42----"
+    );
+
+    // Too narrow: the fill disappears, nothing is cut
+    assert_eq!(
+        format_number_with_width(123456.0, &format, &locale, 3),
+        "123456"
+    );
+
+    // Without a width the fill is ignored as before
+    assert_eq!(format_number(42.0, &format, &locale), "42");
+
+    // Skip width uses the width of the skipped character
+    let format = parse_number_format("0_年").unwrap();
+    assert_eq!(format_number_with_width(7.0, &format, &locale, 0), "7  ");
+
+    // Fractions line up too, with the fill on the side it is written
+    let format = parse_number_format("# ?/?* ").unwrap();
+    assert_eq!(
+        format_number_with_width(3.5, &format, &locale, 12),
+        "3 1/2       "
+    );
+    let format = parse_number_format("* # ?/?").unwrap();
+    assert_eq!(
+        format_number_with_width(3.5, &format, &locale, 12),
+        "       3 1/2"
+    );
+
+    // Options apply as with format_number_with_options
+    let format = parse_number_format("yyyy-mm-dd* ").unwrap();
+    let options = FormatOptions::default()
+        .with_date_system(DateSystem::Excel1904)
+        .with_rounding_mode(RoundingMode::TowardZero);
+    assert_eq!(
+        format_number_with_width_and_options(0.0, &format, &locale, 12, &options),
+        "1904-01-01  "
+    );
+    let format = parse_number_format("* 0.00").unwrap();
+    assert_eq!(
+        format_number_with_width_and_options(1.999, &format, &locale, 6, &options),
+        "  1.99"
+    );
+
+    // Text section padding lines up with the numbers
+    let format = parse_number_format("_(0_);(0);0;_(*.@_)").unwrap();
+    assert_eq!(
+        format_text_with_width("abc", &format, &locale, 8),
+        " ...abc "
+    );
+    assert_eq!(format_number_with_width(5.0, &format, &locale, 8), " 5 ");
+    let format = parse_number_format("0.00").unwrap();
+    assert_eq!(format_text_with_width("abc", &format, &locale, 8), "abc");
+}
+
+#[test]
+fn test_format_text() {
+    let locale = LocaleSettings::default();
+
+    let format = parse_number_format("0;-0;0;[Red]\"Text: \"@").unwrap();
+    assert_eq!(format_text("abc", &format, &locale), "Text: abc");
+
+    // A text section without @ replaces the text
+    let format = parse_number_format("0;-0;0;\"n/a\"").unwrap();
+    assert_eq!(format_text("abc", &format, &locale), "n/a");
+
+    // @ in a single-section format applies to text
+    let format = parse_number_format("\"Name: \"@").unwrap();
+    assert_eq!(format_text("Ada", &format, &locale), "Name: Ada");
+
+    // No text section and no @: the text is shown unchanged
+    for code in ["#,##0.00", "0;-0", "0;-0;\"zero\"", "General", "yyyy-mm-dd"] {
+        let format = parse_number_format(code).unwrap();
+        assert_eq!(format_text("abc", &format, &locale), "abc", "{}", code);
+    }
+}
+
+#[test]
+fn test_format_cell_values() {
+    let locale = LocaleSettings::default();
+    let format = parse_number_format("#,##0.00;[Red]-#,##0.00;\"-\";\"Note: \"@").unwrap();
+
+    let cases = [
+        (CellValue::Number(1234.5), "1,234.50"),
+        (CellValue::Number(0.0), "-"),
+        (CellValue::Text("abc".to_string()), "Note: abc"),
+        (CellValue::Bool(true), "TRUE"),
+        (CellValue::Bool(false), "FALSE"),
+        (CellValue::Error("#DIV/0!".to_string()), "#DIV/0!"),
+        (CellValue::Empty, ""),
+    ];
+    for (value, expected) in cases {
+        assert_eq!(
+            format_value(&value, &format, &locale),
+            expected,
+            "{:?}",
+            value
+        );
+    }
+
+    let french = number_format::get_locale_settings("fr").unwrap();
+    assert_eq!(
+        format_value(&CellValue::Bool(true), &format, &french),
+        "VRAI"
+    );
+    let custom = LocaleSettings::default().with_boolean_names(["yes", "no"]);
+    assert_eq!(
+        format_value(&CellValue::Bool(false), &format, &custom),
+        "no"
+    );
+
+    // Options apply to numbers only
+    let options = FormatOptions::default()
+        .with_rounding_mode(RoundingMode::TowardZero)
+        .with_date_system(DateSystem::Excel1904);
+    assert_eq!(
+        format_value_with_options(&CellValue::Number(-1.999), &format, &locale, &options),
+        "-1.99"
+    );
+    assert_eq!(
+        format_value_with_options(&CellValue::Bool(true), &format, &locale, &options),
+        "TRUE"
+    );
+    let date = parse_number_format("yyyy-mm-dd;@").unwrap();
+    assert_eq!(
+        format_value_with_options(&CellValue::Number(0.0), &date, &locale, &options),
+        "1904-01-01"
+    );
+    assert_eq!(
+        format_value_with_options(&CellValue::Text("x".to_string()), &date, &locale, &options),
+        "x"
+    );
+}
+
+#[test]
+fn test_dbnum_numerals() {
+    let locale = LocaleSettings::default();
+    let format =
+        |code: &str, value: f64| format_number(value, &parse_number_format(code).unwrap(), &locale);
+
+    assert_eq!(format("[DBNum1]General", 123.0), "一百二十三");
+    assert_eq!(format("[DBNum1]0", 15.0), "十五");
+    assert_eq!(format("[DBNum1]0", 101.0), "一百○一");
+    assert_eq!(format("[DBNum1]0", 100000.0), "十万");
+    assert_eq!(
+        format("[DBNum1]0", 123456789.0),
+        "一亿二千三百四十五万六千七百八十九"
+    );
+    assert_eq!(format("[DBNum1]0", 0.0), "○");
+
+    // Financial numerals; grouping is dropped, and a decimal part has the
+    // whole number read digit by digit
+    assert_eq!(format("[DBNum2]#,##0", 20300.0), "贰万零叁佰");
+    assert_eq!(format("[DBNum2]0.00", 1234.5), "壹贰叁肆.伍零");
+    assert_eq!(format("[DBNum1][$-804]0.00", 123.0), "一二三.○○");
+    assert_eq!(format("[DBNum1]General", 1.25), "一.二五");
+
+    // Digits in literals and exponents are not placeholders
+    assert_eq!(format("[DBNum1]0\" m2\"", 12.0), "十二 m2");
+    assert_eq!(format("[DBNum1]\"No.1 \"0", 12.0), "No.1 十二");
+    assert_eq!(format("[DBNum1]0.0E+00", 12345.0), "一.二E+04");
+    assert_eq!(format("[DBNum1]General", 1.5e20), "一.五○○○○○E+20");
+
+    // Full-width digits keep the layout of the section
+    assert_eq!(format("[DBNum3]#,##0.0", 1234.5), "１,２３４.５");
+
+    assert_eq!(format("[DBNum1]# ?/?", 1.5), "一 一/二");
+    assert_eq!(format("[DBNum1]0;[DBNum1]-0", -12.0), "-十二");
+}
+
+#[test]
+fn test_format_decimal() {
+    let locale = LocaleSettings::default();
+    let format = |code: &str, value: &str| {
+        let value: Decimal = value.parse().unwrap();
+        format_decimal(&value, &parse_number_format(code).unwrap(), &locale)
+    };
+
+    // Halves are rounded on the decimal digits, not on the nearest f64
+    assert_eq!(format("0.00", "1.005"), "1.01");
+    assert_eq!(format("0.00", "-1.005"), "-1.01");
+    assert_eq!(format("0", "0.5"), "1");
+    assert_eq!(format("0.00", "0.004"), "0.00");
+    assert_eq!(format("0", "999.5"), "1000");
+    assert_eq!(
+        format("#,##0.00", "12345678901234567890.125"),
+        "12,345,678,901,234,567,890.13"
+    );
+    assert_eq!(format("#,##0", "9007199254740993"), "9,007,199,254,740,993");
+    assert_eq!(format("#.##", "0.001"), "0.");
+
+    // Percent and scaling commas shift the decimal point exactly
+    assert_eq!(format("0.0%", "0.12345"), "12.3%");
+    assert_eq!(format("0.00%", "0.000125"), "0.01%");
+    assert_eq!(format("#,##0.0,,", "1234550000"), "1,234.6");
+
+    // Exponential sections round the significant digits
+    assert_eq!(format("0.00E+00", "12345"), "1.23E+04");
+    assert_eq!(format("0.00E+00", "9.995"), "1.00E+01");
+    assert_eq!(format("0.00E+00", "-0.00012345"), "-1.23E-04");
+    assert_eq!(format("0.0E+00", "1e-400"), "1.0E-400");
+    assert_eq!(format("0.00E+00", "0"), "0.00E+00");
+
+    // Sections are chosen by sign, with the negative section supplying its own sign
+    assert_eq!(format("0.00;(0.00);\"zero\"", "-2.345"), "(2.35)");
+    assert_eq!(format("0.00;(0.00);\"zero\"", "0.000"), "zero");
+    assert_eq!(format("[DBNum1]0", "101"), "一百○一");
+
+    // Other sections see the nearest f64
+    assert_eq!(format("yyyy-mm-dd", "45292"), "2024-01-01");
+    assert_eq!(format("# ?/?", "1.5"), "1 1/2");
+    assert_eq!(format("General", "1.25"), "1.25");
+
+    assert_eq!(
+        format_decimal(
+            &Decimal::new(12345, 2),
+            &parse_number_format("0.0").unwrap(),
+            &locale
+        ),
+        "123.5"
+    );
+}
+
+#[test]
+fn test_decimal_parsing() {
+    assert_eq!("-1234.50".parse::<Decimal>(), Ok(Decimal::new(-12345, 1)));
+    assert_eq!("+.5".parse::<Decimal>(), Ok(Decimal::new(5, 1)));
+    assert_eq!("1.5E3".parse::<Decimal>(), Ok(Decimal::new(1500, 0)));
+    assert_eq!("-0.00".parse::<Decimal>(), Ok(Decimal::new(0, 0)));
+    assert!("-0".parse::<Decimal>().unwrap().is_zero());
+    assert!(!"-0".parse::<Decimal>().unwrap().is_negative());
+    assert_eq!(Decimal::new(-125, 2).to_f64(), -1.25);
+
+    for input in ["", "-", ".", "1.2.3", "1e", "12a", "1 000", "NaN"] {
+        assert!(input.parse::<Decimal>().is_err(), "{input:?}");
+    }
+
+    // Magnitudes that would take gigabytes to lay out are rejected
+    for input in ["1e30000000", "1e2000000000", "-1e-2000000000", "0.1e10002"] {
+        assert!(input.parse::<Decimal>().is_err(), "{input:?}");
+    }
+    assert!("1e10000".parse::<Decimal>().is_ok());
+    assert!("1e-10000".parse::<Decimal>().is_ok());
+    assert!("0e2000000000".parse::<Decimal>().unwrap().is_zero());
+}
+
+#[test]
+fn test_format_integer() {
+    let locale = LocaleSettings::default();
+    let format = |code: &str| parse_number_format(code).unwrap();
+
+    assert_eq!(
+        format_integer(9_007_199_254_740_993_i64, &format("0"), &locale),
+        "9007199254740993"
+    );
+    assert_eq!(
+        format_integer(i64::MIN, &format("#,##0"), &locale),
+        "-9,223,372,036,854,775,808"
+    );
+    assert_eq!(
+        format_integer(u64::MAX, &format("#,##0"), &locale),
+        "18,446,744,073,709,551,615"
+    );
+    assert_eq!(
+        format_integer(i128::MIN, &format("0"), &locale),
+        "-170141183460469231731687303715884105728"
+    );
+    assert_eq!(
+        format_integer(u128::MAX, &format("#,##0"), &locale),
+        "340,282,366,920,938,463,463,374,607,431,768,211,455"
+    );
+
+    // Scaling commas round the dropped digits exactly
+    assert_eq!(
+        format_integer(123_456_789_012_345_678_u64, &format("#,##0,,"), &locale),
+        "123,456,789,012"
+    );
+    assert_eq!(
+        format_integer(999_999_999_999_999_500_u64, &format("#,##0,"), &locale),
+        "1,000,000,000,000,000"
+    );
+
+    // Literals between placeholders
+    assert_eq!(
+        format_integer(123_456_789_u64, &format("000-00-0000"), &locale),
+        "123-45-6789"
+    );
+    assert_eq!(
+        format_integer(1_234_567_890_123_i64, &format("000-00-0000"), &locale),
+        "1234567-89-0123"
+    );
+    assert_eq!(
+        format_integer(42_u64, &format("000-00-0000"), &locale),
+        "000-00-0042"
+    );
+
+    // Fractions keep every digit of the whole number
+    assert_eq!(
+        format_integer(u64::MAX, &format("# ?/?"), &locale),
+        "18446744073709551615   "
+    );
+    assert_eq!(
+        format_decimal(
+            &"-9007199254740993.5".parse().unwrap(),
+            &format("# ?/?"),
+            &locale
+        ),
+        "-9007199254740993 1/2"
+    );
+    assert_eq!(
+        format_decimal(
+            &"99999999999999999999.96".parse().unwrap(),
+            &format("# ?/?"),
+            &locale
+        ),
+        "100000000000000000000   "
+    );
+    assert_eq!(
+        format_number(1e20, &format("# ?/?"), &locale),
+        "100000000000000000000   "
+    );
+
+    assert_eq!(
+        format_integer(-42_i64, &format("0;(0);\"zero\""), &locale),
+        "(42)"
+    );
+    assert_eq!(
+        format_integer(0_u64, &format("0;(0);\"zero\""), &locale),
+        "zero"
+    );
+    assert_eq!(
+        format_integer(12_345_678_901_234_567_i64, &format("0.00E+00"), &locale),
+        "1.23E+16"
+    );
+
+    assert_eq!(
+        format_number(1_234_567_890_123.0, &format("000-00-0000"), &locale),
+        "1234567-89-0123"
+    );
+
+    // The f64 path no longer saturates beyond i64::MAX
+    assert_eq!(
+        format_number(1e20, &format("#,##0"), &locale),
+        "100,000,000,000,000,000,000"
+    );
+}
+
+#[test]
+fn test_excel_precision() {
+    let locale = LocaleSettings::default();
+    let excel = FormatOptions::default().with_excel_precision(true);
+    let format = |code: &str, value: f64, options: &FormatOptions| {
+        format_number_with_options(value, &parse_number_format(code).unwrap(), &locale, options)
+    };
+
+    let sum = 0.1 + 0.2;
+    assert_eq!(
+        format("0.00000000000000000", sum, &FormatOptions::default()),
+        "0.30000000000000004"
+    );
+    assert_eq!(
+        format("0.00000000000000000", sum, &excel),
+        "0.30000000000000000"
+    );
+    assert_eq!(
+        format("0.00000000000000000", -sum, &excel),
+        "-0.30000000000000000"
+    );
+    assert_eq!(
+        format("0.0000000000000000%", sum, &excel),
+        "30.0000000000000000%"
+    );
+    assert_eq!(
+        format("0.00000000000000000E+00", sum, &excel),
+        "3.00000000000000000E-01"
+    );
+    assert_eq!(
+        format("#,##0", 1234567890123456789.0, &excel),
+        "1,234,567,890,123,460,000"
+    );
+
+    // Halves that the double stores just below .5 round up, as Excel shows them
+    assert_eq!(format("0.00", 1.005, &excel), "1.01");
+    assert_eq!(format("0.0", 0.15, &excel), "0.2");
+    assert_eq!(format("0.00;(0.00)", -1.005, &excel), "(1.01)");
+
+    // Fractions and General see the rounded value too
+    assert_eq!(format("General", 1.0 - 1e-16, &excel), "1");
+    assert_eq!(format("# ?/?", 0.1 + 0.2 + 0.2, &excel), " 1/2");
+    assert_eq!(
+        format("0.00", f64::INFINITY, &excel),
+        format("0.00", f64::INFINITY, &FormatOptions::default())
+    );
+}
+
+#[test]
+fn test_rounding_modes() {
+    let locale = LocaleSettings::default();
+    let format = |code: &str, value: f64, mode: RoundingMode| {
+        let options = FormatOptions::default().with_rounding_mode(mode);
+        format_number_with_options(
+            value,
+            &parse_number_format(code).unwrap(),
+            &locale,
+            &options,
+        )
+    };
+    use RoundingMode::*;
+
+    let cases = [
+        (HalfUp, ["3", "-3", "2", "3"]),
+        (HalfEven, ["2", "-2", "2", "3"]),
+        (HalfDown, ["2", "-2", "2", "3"]),
+        (TowardZero, ["2", "-2", "2", "2"]),
+        (AwayFromZero, ["3", "-3", "3", "3"]),
+        (Floor, ["2", "-3", "2", "2"]),
+        (Ceiling, ["3", "-2", "3", "3"]),
+    ];
+    for (mode, expected) in cases {
+        let actual = [2.5, -2.5, 2.1, 2.9].map(|value| format("0", value, mode));
+        assert_eq!(actual, expected, "{mode:?}");
+    }
+
+    // Ties are decided on the decimal the double stands for
+    assert_eq!(format("0.00", 2.345, HalfEven), "2.34");
+    assert_eq!(format("0.00", 2.335, HalfEven), "2.34");
+    assert_eq!(format("#,##0.00", 1234.565, HalfDown), "1,234.56");
+    assert_eq!(format("0.0%", 0.12345, HalfEven), "12.3%");
+    assert_eq!(format("0.00;(0.00)", -1.239, TowardZero), "(1.23)");
+    assert_eq!(format("0.00;(0.00)", -1.231, Floor), "(1.24)");
+    assert_eq!(format("0", 0.2, Ceiling), "1");
+    assert_eq!(format("0", -0.2, Ceiling), "-0");
+
+    // Exponential mantissas
+    assert_eq!(format("0.00E+00", 12350.0, HalfEven), "1.24E+04");
+    assert_eq!(format("0.00E+00", 12250.0, HalfEven), "1.22E+04");
+    assert_eq!(format("0.00E+00", 99999.0, TowardZero), "9.99E+04");
+    assert_eq!(format("0.00E+00", -12341.0, Floor), "-1.24E+04");
+
+    // Fractions with fixed and free denominators; the fixed one is escaped for the parser
+    assert_eq!(format("# ?/\\4", 1.125, HalfUp), "1 1/4");
+    assert_eq!(format("# ?/\\4", 1.125, HalfEven), "1  ");
+    assert_eq!(format("# ?/\\4", 1.2, Ceiling), "1 1/4");
+    assert_eq!(format("# ?/?", 0.99, HalfUp), "1   ");
+    assert_eq!(format("# ?/?", 0.99, TowardZero), " 8/9");
+    assert_eq!(format("# ?/?", 0.34, Floor), " 1/3");
+    assert_eq!(format("# ?/?", 0.34, Ceiling), " 3/8");
+
+    // Fractional seconds of durations and times
+    let half_second = 0.5 / 86400.0;
+    assert_eq!(
+        format("[h]:mm:ss", 1.0 / 24.0 + half_second, HalfUp),
+        "1:00:01"
+    );
+    assert_eq!(
+        format("[h]:mm:ss", 1.0 / 24.0 + half_second, HalfEven),
+        "1:00:00"
+    );
+    assert_eq!(
+        format("[h]:mm:ss.0", 1.56 / 86400.0, TowardZero),
+        "0:00:01.5"
+    );
+    assert_eq!(format("hh:mm:ss", 0.5 + 1.7 / 86400.0, Floor), "12:00:01");
+    assert_eq!(
+        format("hh:mm:ss.00", 0.5 + 1.456 / 86400.0, HalfUp),
+        "12:00:01.46"
+    );
+    assert_eq!(
+        format("hh:mm:ss.00", 0.5 + 1.456 / 86400.0, TowardZero),
+        "12:00:01.45"
+    );
+}
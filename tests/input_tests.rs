@@ -0,0 +1,185 @@
+use number_format::input::{InputError, parse_value, parse_value_with_options};
+use number_format::parser::parse_number_format;
+use number_format::types::{DateSystem, FormatOptions, LocaleSettings};
+use number_format::{format_number, format_number_with_options, get_locale_settings};
+
+fn parse(input: &str, code: &str) -> Result<f64, InputError> {
+    parse_value(
+        input,
+        &parse_number_format(code).unwrap(),
+        &LocaleSettings::default(),
+    )
+}
+
+#[test]
+fn test_parse_numbers() {
+    assert_eq!(parse("1,234.50", "#,##0.00"), Ok(1234.5));
+    assert_eq!(parse("(1,234.50)", "#,##0.00;(#,##0.00)"), Ok(-1234.5));
+    assert_eq!(parse("-1,234.50", "#,##0.00"), Ok(-1234.5));
+    assert_eq!(parse("  42 ", "0"), Ok(42.0));
+    assert_eq!(parse("5", "0.##"), Ok(5.0));
+    assert_eq!(parse("12.5%", "0.0%"), Ok(0.125));
+    assert_eq!(parse("12M", "#,##0,,\"M\""), Ok(12_000_000.0));
+    assert_eq!(parse("1.23E+04", "0.00E+00"), Ok(12300.0));
+    assert_eq!(parse("1.5E-3", "0.0E+0"), Ok(0.0015));
+    assert_eq!(parse("123-45-6789", "000-00-0000"), Ok(123456789.0));
+    assert_eq!(parse("2.75 kg", "0.00\" kg\""), Ok(2.75));
+    assert_eq!(parse("-1.5E+20", "General"), Ok(-1.5e20));
+    assert_eq!(parse("0.1", "General"), Ok(0.1));
+}
+
+#[test]
+fn test_parse_currency_and_locale() {
+    let accounting = "_($* #,##0.00_);_($* (#,##0.00);_($* \"-\"??_);_(@_)";
+    assert_eq!(parse(" $   1,234.50 ", accounting), Ok(1234.5));
+    assert_eq!(parse(" $  (1,234.50)", accounting), Ok(-1234.5));
+    assert_eq!(parse(" $ - ", accounting), Ok(0.0));
+    assert_eq!(parse("€ 5.00", "[$€-407] #,##0.00"), Ok(5.0));
+
+    let german = LocaleSettings::default()
+        .with_decimal_point(',')
+        .with_thousands_separator('.');
+    let format = parse_number_format("#,##0.00").unwrap();
+    assert_eq!(parse_value("1.234,50", &format, &german), Ok(1234.5));
+    let format = parse_number_format("[$]#,##0.00").unwrap();
+    let euro = german.clone().with_currency_symbol("€".to_string());
+    assert_eq!(parse_value("€1.234,50", &format, &euro), Ok(1234.5));
+}
+
+#[test]
+fn test_parse_fractions() {
+    assert_eq!(parse("1 3/4", "# ?/?"), Ok(1.75));
+    assert_eq!(parse(" 3/4", "# ?/?"), Ok(0.75));
+    assert_eq!(parse("2   ", "# ?/?"), Ok(2.0));
+    assert_eq!(parse("7/4", "?/?"), Ok(1.75));
+}
+
+#[test]
+fn test_parse_dates_and_times() {
+    assert_eq!(parse("12.03.2024", "dd.mm.yyyy"), Ok(45363.0));
+    assert_eq!(parse("1/2/24", "m/d/yy"), Ok(45293.0));
+    assert_eq!(parse("6 may 2024", "d mmm yyyy"), Ok(45418.0));
+    assert_eq!(
+        parse("Monday, May 6, 2024", "dddd, mmmm d, yyyy"),
+        Ok(45418.0)
+    );
+    assert_eq!(parse("20240506", "yyyymmdd"), Ok(45418.0));
+    assert_eq!(parse("2:30 PM", "h:mm AM/PM"), Ok(14.5 / 24.0));
+    assert_eq!(parse("12:00 am", "h:mm AM/PM"), Ok(0.0));
+    assert_eq!(
+        parse("2024-05-06 12:00:00.500", "yyyy-mm-dd hh:mm:ss.000"),
+        Ok(45418.0 + 43200.5 / 86400.0)
+    );
+    assert_eq!(parse("36:00:00", "[h]:mm:ss"), Ok(1.5));
+    assert_eq!(parse("90:30", "[mm]:ss"), Ok(5430.0 / 86400.0));
+
+    // Names come from the locale, or from the section's locale tag
+    let french = get_locale_settings("fr").unwrap();
+    let format = parse_number_format("d mmmm yyyy").unwrap();
+    let text = format_number(45418.0, &format, &french);
+    assert_eq!(parse_value(&text, &format, &french), Ok(45418.0));
+    assert_eq!(parse("6. Mai 2024", "[$-407]d. mmmm yyyy"), Ok(45418.0));
+}
+
+#[test]
+fn test_parse_with_options() {
+    let locale = LocaleSettings::default();
+    let format = parse_number_format("yyyy-mm-dd hh:mm").unwrap();
+    let excel_1904 = FormatOptions::default().with_date_system(DateSystem::Excel1904);
+    assert_eq!(
+        parse_value_with_options("1904-01-02 12:00", &format, &locale, &excel_1904),
+        Ok(1.5)
+    );
+    assert_eq!(
+        parse_value_with_options("1903-12-31 00:00", &format, &locale, &excel_1904),
+        Err(InputError::InvalidDate)
+    );
+
+    // Proleptic dates read back as negative serials
+    let proleptic = FormatOptions::default().with_proleptic_dates(true);
+    assert_eq!(
+        parse_value_with_options("1899-12-29 18:00", &format, &locale, &proleptic),
+        Ok(-0.25)
+    );
+    assert_eq!(
+        parse_value("1899-12-29 18:00", &format, &locale),
+        Err(InputError::InvalidDate)
+    );
+    for (options, value) in [
+        (excel_1904.clone(), 45000.5),
+        (proleptic.clone(), -36522.0),
+        (excel_1904.with_proleptic_dates(true), -1000.75),
+    ] {
+        let text = format_number_with_options(value, &format, &locale, &options);
+        assert_eq!(
+            parse_value_with_options(&text, &format, &locale, &options),
+            Ok(value),
+            "{}",
+            text
+        );
+    }
+}
+
+#[test]
+fn test_parse_round_trip() {
+    let locale = LocaleSettings::default();
+    for (code, value) in [
+        ("#,##0.00;[Red](#,##0.00)", -98765.43),
+        ("0.000%", 0.12345),
+        ("0.00E+00", 6.02e23),
+        ("yyyy-mm-dd hh:mm:ss", 45418.75),
+        ("[h]:mm", 2.25),
+        ("\"Total: \"#,##0", 1234567.0),
+    ] {
+        let format = parse_number_format(code).unwrap();
+        let text = format_number(value, &format, &locale);
+        assert_eq!(parse_value(&text, &format, &locale), Ok(value), "{}", code);
+    }
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(parse("   ", "0.00"), Err(InputError::Empty));
+    assert_eq!(parse("12x", "0"), Err(InputError::Mismatch { position: 2 }));
+    assert_eq!(
+        parse("$5", "#,##0.00;(#,##0.00)"),
+        Err(InputError::Mismatch { position: 0 })
+    );
+    assert_eq!(
+        parse("31.02.2024", "dd.mm.yyyy"),
+        Err(InputError::InvalidDate)
+    );
+    assert_eq!(
+        parse("13:00 PM", "h:mm AM/PM"),
+        Err(InputError::InvalidDate)
+    );
+    // Elapsed times too large for the arithmetic are not valid durations
+    assert_eq!(
+        parse("9999999999999999:00", "[h]:mm"),
+        Err(InputError::InvalidDate)
+    );
+    assert_eq!(
+        parse("99999999999999999999999:00", "[h]:mm"),
+        Err(InputError::InvalidDate)
+    );
+    assert_eq!(parse("abc", "@"), Err(InputError::Unsupported));
+    assert_eq!(parse("5", "[DBNum1]0"), Err(InputError::Unsupported));
+    // Conditions limit the values a section can stand for
+    assert_eq!(parse("150", "[>100]0;[<=100]\"small\""), Ok(150.0));
+    assert_eq!(
+        parse("50", "[>100]0;[<=100]\"small\""),
+        Err(InputError::Mismatch { position: 0 })
+    );
+    // Numbers beyond the range of an f64 are not read as infinity
+    assert!(matches!(
+        parse("1E+999", "0.00E+00"),
+        Err(InputError::Mismatch { .. })
+    ));
+    assert!(matches!(
+        parse("1E+999", "General"),
+        Err(InputError::Mismatch { .. })
+    ));
+    // With a condition, the second section is not a negative section
+    assert_eq!(parse("5", "[>10]0;0"), Ok(5.0));
+    assert_eq!(parse("-5", "[>10]0;0"), Ok(-5.0));
+}
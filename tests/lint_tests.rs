@@ -0,0 +1,111 @@
+use number_format::lint::{LintWarning, lint};
+use number_format::parser::parse_number_format;
+
+fn lint_code(code: &str) -> Vec<LintWarning> {
+    lint(&parse_number_format(code).unwrap())
+}
+
+#[test]
+fn test_lint_clean_formats() {
+    for code in [
+        "#,##0.00;[Red]-#,##0.00;0.00;\"Text: \"@",
+        "yyyy-mm-dd hh:mm:ss.000",
+        "[h]:mm:ss",
+        "0.00E+00",
+        "_(#,##0_);_(\\(#,##0\\);_(\"-\"??_);_(@_)",
+        "#,##0_);(#,##0)",
+        "[>=100]0;[<0]-0",
+    ] {
+        assert_eq!(lint_code(code), vec![], "{}", code);
+    }
+}
+
+#[test]
+fn test_lint_ambiguous_month() {
+    assert_eq!(
+        lint_code("hh.mm"),
+        vec![LintWarning::AmbiguousMonth {
+            section: 0,
+            token: 2
+        }]
+    );
+    assert_eq!(
+        lint_code("mm\"h\" hh"),
+        vec![LintWarning::AmbiguousMonth {
+            section: 0,
+            token: 0
+        }]
+    );
+}
+
+#[test]
+fn test_lint_section_conditions() {
+    assert_eq!(
+        lint_code("[>=0]0;[>10]0.0"),
+        vec![LintWarning::UnreachableSection { section: 1 }]
+    );
+    assert_eq!(
+        lint_code("[>=0]0;[<0]-0;\"zero\""),
+        vec![LintWarning::UnreachableSection { section: 2 }]
+    );
+    assert_eq!(
+        lint_code("[>100]0;[<-100]-0;0.0"),
+        vec![LintWarning::ZeroSectionAfterConditions { section: 2 }]
+    );
+}
+
+#[test]
+fn test_lint_ignored_date_placeholders() {
+    assert_eq!(
+        lint_code("yyyy#dd0 ss.00%"),
+        vec![
+            LintWarning::IgnoredInDateSection {
+                section: 0,
+                token: 1
+            },
+            LintWarning::IgnoredInDateSection {
+                section: 0,
+                token: 3
+            },
+            LintWarning::IgnoredInDateSection {
+                section: 0,
+                token: 9
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_lint_stray_exponent() {
+    assert_eq!(
+        lint_code("E+00"),
+        vec![LintWarning::StrayExponent {
+            section: 0,
+            token: 0
+        }]
+    );
+    assert_eq!(
+        lint_code("0.0E+"),
+        vec![LintWarning::StrayExponent {
+            section: 0,
+            token: 3
+        }]
+    );
+}
+
+#[test]
+fn test_lint_unbalanced_padding() {
+    let warnings = lint_code("_(#,##0;-0");
+    assert_eq!(
+        warnings,
+        vec![LintWarning::UnbalancedPadding { section: 0 }]
+    );
+    assert_eq!(
+        lint_code("0_)"),
+        vec![LintWarning::UnbalancedPadding { section: 0 }]
+    );
+    assert_eq!(
+        warnings[0].to_string(),
+        "'_(' or '_)' padding has no matching parenthesis (section 1)"
+    );
+}
@@ -213,7 +213,111 @@ fn test_condition_limits() {
     assert!(res.is_err());
     assert_eq!(
         res.unwrap_err(),
-        "Format string cannot have more than two conditional sections."
+        ParseError::TooManyConditions {
+            section: 2,
+            span: 13..17
+        }
+    );
+}
+
+#[test]
+fn test_parse_error_spans() {
+    let err = parse_number_format("0.00;\"abc").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::UnterminatedQuote {
+            section: 1,
+            span: 5..9
+        }
+    );
+
+    let err = parse_number_format("#,##0;[Foo]-0").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::UnknownBracketTag {
+            section: 1,
+            span: 6..11
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "Unknown bracket tag at bytes 6..11 (section 2)"
+    );
+
+    let err = parse_number_format("[Red0").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::UnterminatedBracket {
+            section: 0,
+            span: 0..5
+        }
+    );
+
+    let err = parse_number_format("0x").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::UnexpectedCharacter {
+            section: 0,
+            span: 1..2,
+            found: 'x'
+        }
+    );
+
+    let err = parse_number_format("0*").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::DanglingModifier {
+            section: 0,
+            span: 1..2
+        }
+    );
+
+    let err = parse_number_format("0;0;0;@;0").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::TooManySections {
+            section: 3,
+            span: 7..9
+        }
+    );
+    assert!(err.to_string().ends_with("(section 4)"));
+
+    let err = parse_number_format(";;;\"a\"0").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::NumericTokenInTextSection {
+            section: 3,
+            span: 6..7
+        }
+    );
+    assert_eq!(err.section(), 3);
+    assert_eq!(err.span(), 6..7);
+
+    // Spans follow the tokens the section was parsed with, past any condition
+    let options = ParseOptions::default().with_extended_tokens(true);
+    assert_eq!(
+        parse_number_format_with_options(";;;@ jjj", &options),
+        Err(ParseError::NumericTokenInTextSection {
+            section: 3,
+            span: 5..8
+        })
+    );
+    assert_eq!(
+        parse_number_format("[>1]0e"),
+        Err(ParseError::UnexpectedCharacter {
+            section: 0,
+            span: 5..6,
+            found: 'e'
+        })
+    );
+
+    let err = parse_number_format("0;0;0;[>0]@").unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::ConditionInTextSection {
+            section: 3,
+            span: 6..10
+        }
     );
 }
 
@@ -283,3 +387,392 @@ fn test_main() {
         }
     }
 }
+
+#[test]
+fn test_to_format_code_round_trip() {
+    let codes = [
+        "0.00",
+        "#,##0.00;[Red]-#,##0.00;0.00;\"Text: \"@",
+        "[>=1000]#,##0;[<1000]0.0;0;@",
+        "0,,\"M\"",
+        "#,##0.0, \"K\"",
+        "\"a\"\\\"\"b\"",
+        "\\#0\\_0",
+        "yyyy/mm/dd \"at\" hh:mm AM/PM",
+        "mmm d, yyyy h:mm:ss.000",
+        "[h]:mm:ss",
+        "_($* #,##0.00_)",
+        "[$US-409]#,##0.00",
+        "¤#,##0.00",
+        "0.00E+00",
+        "# ??/??",
+        "# ?/16",
+        "#\\ ?/4",
+        "?/100",
+        "0%%",
+        "[<-5.5][Blue]0;0",
+        ";;;",
+    ];
+    for code in codes {
+        let parsed = parse_number_format(code).unwrap();
+        let emitted = parsed.to_format_code();
+        assert_eq!(
+            parse_number_format(&emitted).unwrap(),
+            parsed,
+            "round trip of {code:?} via {emitted:?}"
+        );
+    }
+}
+
+#[test]
+fn test_to_format_code_canonical_output() {
+    let code = |s: &str| parse_number_format(s).unwrap().to_format_code();
+    assert_eq!(code("#,##0.00;[RED]-#,##0.00"), "#,##0.00;[Red]-#,##0.00");
+    assert_eq!(code("0.0,,"), "0.0,,");
+    assert_eq!(code("[$€-407]0.00"), "[$€-407]0.00");
+    assert_eq!(code("YYYY-MM-DD"), "yyyy-mm-dd");
+    assert_eq!(code("0\\x"), "0\\x");
+    assert_eq!(code("[>=100]0;0"), "[>=100]0;0");
+
+    // Written the way Excel reads it
+    assert_eq!(code("# ?/16"), "# ?/16");
+    assert_eq!(code("#\\ ??/100"), "# ??/100");
+    assert_eq!(code("# ?/\\1\\6"), "# ?/16");
+    assert_eq!(code("\"a\"\\\"\"b\""), "\"a\"\\\"\"b\"");
+    assert_eq!(code("\"\"0"), "\"\"0");
+    // This crate also reads escapes inside quotes; Excel does not, so they are written outside
+    assert_eq!(
+        code("\"hello \\\"world\\\\ \""),
+        "\"hello \"\\\"\"world\"\\\\\" \""
+    );
+
+    let mut edited = parse_number_format("0.00").unwrap();
+    edited
+        .positive_section
+        .tokens
+        .push(FormatToken::DigitOrZero);
+    edited
+        .positive_section
+        .tokens
+        .push(FormatToken::QuotedText(" kg".to_string()));
+    assert_eq!(edited.to_format_code(), "0.000\" kg\"");
+
+    // Excel reads a backslash inside quotes as itself, so it is written outside them
+    let mut edited = parse_number_format("@").unwrap();
+    edited
+        .positive_section
+        .tokens
+        .insert(0, FormatToken::QuotedText("C:\\temp\\".to_string()));
+    assert_eq!(edited.to_format_code(), "\"C:\"\\\\\"temp\"\\\\@");
+}
+
+#[test]
+fn test_cst_preserves_spelling_and_spans() {
+    let code = "[>=100][RED]YYYY\\x\"q\" mm;[$US-409]0.0";
+    let cst = parse_cst(code).unwrap();
+    assert_eq!(cst.to_source(), code);
+
+    let texts: Vec<&str> = cst.tokens.iter().map(|t| t.text.as_str()).collect();
+    assert_eq!(
+        texts,
+        vec![
+            "[>=100]",
+            "[RED]",
+            "YYYY",
+            "\\x",
+            "\"q\"",
+            " ",
+            "mm",
+            ";",
+            "[$US-409]",
+            "0",
+            ".",
+            "0"
+        ]
+    );
+
+    // Spans cover the input without gaps
+    let mut expected_start = 0;
+    for token in &cst.tokens {
+        assert_eq!(token.span.start, expected_start);
+        assert_eq!(&code[token.span.clone()], token.text);
+        expected_start = token.span.end;
+    }
+    assert_eq!(expected_start, code.len());
+
+    assert_eq!(
+        cst.tokens[0].kind,
+        CstTokenKind::Condition(Condition {
+            operator: ComparisonOperator::Ge,
+            value: 100.0
+        })
+    );
+    assert_eq!(
+        cst.tokens[3].kind,
+        CstTokenKind::Token(FormatToken::LiteralChar('x'))
+    );
+    assert_eq!(
+        cst.tokens[6].kind,
+        CstTokenKind::Token(FormatToken::MonthNumPadded)
+    );
+    assert_eq!(cst.tokens[7].kind, CstTokenKind::SectionSeparator);
+    assert_eq!(cst.section_tokens(1).count(), 4);
+
+    assert_eq!(
+        parse_cst("0;\"abc").unwrap_err(),
+        ParseError::UnterminatedQuote {
+            section: 1,
+            span: 2..6
+        }
+    );
+}
+
+#[test]
+fn test_general_token() {
+    let result = parse_number_format("[Red]General;[Blue]-General;0;\"general\"@").unwrap();
+    assert_eq!(result.positive_section.color, Some(ColorType::Red));
+    assert_eq!(
+        result.positive_section.tokens,
+        vec![FormatToken::GeneralNumeric]
+    );
+    assert_eq!(
+        result.negative_section.unwrap().tokens,
+        vec![FormatToken::LiteralChar('-'), FormatToken::GeneralNumeric]
+    );
+    assert_eq!(
+        result.text_section.unwrap().tokens,
+        vec![
+            FormatToken::QuotedText("general".to_string()),
+            FormatToken::TextValue
+        ]
+    );
+
+    let result = parse_number_format("general").unwrap();
+    assert_eq!(
+        result.positive_section.tokens,
+        vec![FormatToken::GeneralNumeric]
+    );
+    assert!(result.text_section.is_none());
+    assert_eq!(result.to_format_code(), "General");
+
+    // General is allowed in the text section, where it shows the text
+    assert!(parse_number_format("0;0;0;General").is_ok());
+    assert!(parse_cst("General\" units\"").is_ok());
+}
+
+#[test]
+fn test_indexed_colors() {
+    let result = parse_number_format("[Color10]#,##0;[color3]-#,##0").unwrap();
+    assert_eq!(result.positive_section.color, Some(ColorType::Indexed(10)));
+    assert_eq!(
+        result.negative_section.as_ref().unwrap().color,
+        Some(ColorType::Indexed(3))
+    );
+    assert_eq!(result.to_format_code(), "[Color10]#,##0;[Color3]-#,##0");
+
+    for code in ["[Color0]0", "[Color57]0", "[Color]0"] {
+        assert!(matches!(
+            parse_number_format(code),
+            Err(ParseError::UnknownBracketTag { .. })
+        ));
+    }
+}
+
+#[test]
+fn test_color_palette() {
+    let palette = ColorPalette::default();
+    assert_eq!(palette.rgb(&ColorType::Indexed(10)), Some(0x008000));
+    assert_eq!(palette.rgb(&ColorType::Red), Some(0xFF0000));
+    assert_eq!(palette.rgb(&ColorType::Indexed(56)), Some(0x333333));
+    assert_eq!(palette.rgb(&ColorType::Indexed(57)), None);
+
+    // Named colors follow the workbook palette
+    let palette = ColorPalette::default()
+        .with_color(3, 0xC00000)
+        .with_color(10, 0x123456);
+    assert_eq!(palette.rgb(&ColorType::Red), Some(0xC00000));
+    assert_eq!(palette.rgb(&ColorType::Indexed(10)), Some(0x123456));
+}
+
+#[test]
+fn test_calendar_tags() {
+    let result = parse_number_format("B2dd/mm/yyyy").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[0],
+        FormatToken::Calendar(CalendarType::Hijri)
+    );
+    let result = parse_number_format("b1d-m-yy").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[0],
+        FormatToken::Calendar(CalendarType::Gregorian)
+    );
+
+    // The calendar byte of a locale code is decoded when formatting
+    let result = parse_number_format("[$-1060000]d/mm/yyyy").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[0],
+        FormatToken::LocaleTag("1060000".to_string())
+    );
+    assert_eq!(result.to_format_code(), "[$-1060000]d/mm/yyyy");
+    let result = parse_number_format("[$-107041E]d/mm/yyyy").unwrap();
+    assert_eq!(
+        parse_number_format(&result.to_format_code()).unwrap(),
+        result
+    );
+
+    assert_eq!(CalendarType::from_locale_code("409"), None);
+    assert_eq!(
+        CalendarType::from_locale_code("1060401"),
+        Some(CalendarType::Hijri)
+    );
+}
+
+#[test]
+fn test_japanese_era_tokens() {
+    let result = parse_number_format("[$-411]ggge\"年\"").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[1..3],
+        [FormatToken::EraNameFull, FormatToken::EraYear]
+    );
+    let result = parse_number_format("gg ee g").unwrap();
+    assert_eq!(
+        result.positive_section.tokens,
+        vec![
+            FormatToken::EraNameAbbr,
+            FormatToken::LiteralChar(' '),
+            FormatToken::EraYearPadded,
+            FormatToken::LiteralChar(' '),
+            FormatToken::EraNameShort,
+        ]
+    );
+
+    // E+ and E- remain exponents
+    let result = parse_number_format("0.0E+0").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[3],
+        FormatToken::Exponential(ExponentialNotation::Plus)
+    );
+    let result = parse_number_format("ee\\-mm\\-dd").unwrap();
+    assert_eq!(result.to_format_code(), "ee\\-mm-dd");
+    assert_eq!(
+        parse_number_format(&result.to_format_code()).unwrap(),
+        result
+    );
+}
+
+#[test]
+fn test_dbnum_tags() {
+    let result = parse_number_format("[DBNum2][$-804]General").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[0],
+        FormatToken::DbNum(DbNumStyle::DbNum2)
+    );
+    let result = parse_number_format("[dbnum1]yyyy\"年\"").unwrap();
+    assert_eq!(result.to_format_code(), "[DBNum1]yyyy\"年\"");
+    assert!(parse_number_format("[DBNum4]0").is_err());
+}
+
+#[test]
+fn test_locale_tags() {
+    let result = parse_number_format("[$-407]mmmm").unwrap();
+    assert_eq!(
+        result.positive_section.tokens[0],
+        FormatToken::LocaleTag("407".to_string())
+    );
+    assert_eq!(result.to_format_code(), "[$-407]mmmm");
+    // Without a code the tag stands for the locale's currency symbol
+    assert_eq!(
+        parse_number_format("[$]0").unwrap().to_format_code(),
+        "[$]0"
+    );
+    assert_eq!(
+        parse_number_format("¤#,##0;(¤#,##0)")
+            .unwrap()
+            .to_format_code(),
+        "[$]#,##0;([$]#,##0)"
+    );
+    // A symbol without a code stays a currency symbol
+    for (code, symbol) in [("[$€]#,##0", "€"), ("[$USD] #,##0", "USD")] {
+        let result = parse_number_format(code).unwrap();
+        assert_eq!(
+            result.positive_section.tokens[0],
+            FormatToken::CurrencySymbolLocalePrefixed(symbol.to_string())
+        );
+        assert_eq!(result.to_format_code(), code);
+        assert_eq!(
+            parse_number_format(&result.to_format_code()).unwrap(),
+            result
+        );
+    }
+    let result = parse_number_format("[$€-407]#,##0;-0").unwrap();
+    assert_eq!(result.to_format_code(), "[$€-407]#,##0;-0");
+}
+
+#[test]
+fn test_extended_tokens() {
+    let options = ParseOptions::default().with_extended_tokens(true);
+
+    // Off by default, so Excel codes keep their meaning
+    assert!(parse_number_format("Q yyyy").is_err());
+    assert!(parse_number_format("\"Q\"0").is_ok());
+
+    let result = parse_number_format_with_options("Q QQ WW IYYY j jjj", &options).unwrap();
+    assert_eq!(
+        result
+            .positive_section
+            .tokens
+            .iter()
+            .filter(|token| **token != FormatToken::LiteralChar(' '))
+            .collect::<Vec<_>>(),
+        vec![
+            &FormatToken::Quarter,
+            &FormatToken::QuarterName,
+            &FormatToken::IsoWeek,
+            &FormatToken::IsoWeekYear,
+            &FormatToken::DayOfYear,
+            &FormatToken::DayOfYearPadded,
+        ]
+    );
+    assert!(result.positive_section.has_datetime);
+    assert_eq!(result.to_format_code(), "Q QQ WW IYYY j jjj");
+
+    // Excel tokens are unaffected
+    let result = parse_number_format_with_options("yyyy-mm-dd;0.00E+00", &options).unwrap();
+    assert_eq!(result, parse_number_format("yyyy-mm-dd;0.00E+00").unwrap());
+
+    // The concrete syntax tree and lint accept the same dialect
+    assert!(parse_cst("Q yyyy").is_err());
+    let cst = parse_cst_with_options("Q yyyy;jjj", &options).unwrap();
+    assert_eq!(cst.to_source(), "Q yyyy;jjj");
+    assert_eq!(
+        cst.tokens[0].kind,
+        CstTokenKind::Token(FormatToken::Quarter)
+    );
+    assert_eq!(cst.tokens[2].span, 2..6);
+    assert_eq!(
+        cst.tokens[4].kind,
+        CstTokenKind::Token(FormatToken::DayOfYearPadded)
+    );
+    assert_eq!(cst.tokens[4].section, 1);
+    let format = parse_number_format_with_options("Q yyyy", &options).unwrap();
+    assert!(number_format::lint::lint(&format).is_empty());
+
+    // Number sections have no era tokens, so `e` and a bare `E` are errors there
+    for (code, span, found) in [
+        ("0e", 1..2, 'e'),
+        ("0.0E0", 3..4, 'E'),
+        ("#,##0 g", 6..7, 'g'),
+    ] {
+        assert_eq!(
+            parse_number_format(code),
+            Err(ParseError::UnexpectedCharacter {
+                section: 0,
+                span,
+                found
+            }),
+            "{}",
+            code
+        );
+    }
+    assert!(parse_number_format("0;[$-411]ggge").is_ok());
+}